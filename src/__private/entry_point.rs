@@ -1,7 +1,11 @@
 use core::ffi::{c_char, c_int};
+#[cfg(feature = "rt-single-thread")]
+use core::future::Future;
 
 use crate::entry_point::Terminate;
 use crate::CharStar;
+#[cfg(feature = "rt-single-thread")]
+use crate::Errno;
 
 /// Computes the size of a null-terminated array.
 ///
@@ -9,7 +13,7 @@ use crate::CharStar;
 ///
 /// This function assumes that `array` contains a null pointer. This null pointer must be within
 /// the allocated block of memory that `array` is a part of.
-unsafe fn null_terminated_array_len(array: *const *const c_char) -> usize {
+pub(crate) unsafe fn null_terminated_array_len(array: *const *const c_char) -> usize {
     unsafe {
         let mut p = array;
         while !(*p).is_null() {
@@ -31,11 +35,83 @@ where
     F: FnOnce(&'static [&'static CharStar], &'static [&'static CharStar]) -> R,
     R: Terminate,
 {
+    unsafe {
+        let (args, env) = make_slices(argv, envp);
+        f(args, env).terminate()
+    }
+}
+
+/// Calls the asynchronous function `f` using the provided `argv` and `envp` pointers, and drives
+/// the returned future to completion on the single-threaded runtime.
+///
+/// # Safety
+///
+/// Both `argv` and `envp` are assumed to be null-terminated arrays of null-terminated strings.
+/// The data referenced by these arrays and strings must remain valid for the lifetime of the
+/// entire program.
+#[cfg(feature = "rt-single-thread")]
+pub unsafe fn call_async<F, Fut>(
+    f: F,
+    argv: *const *const c_char,
+    envp: *const *const c_char,
+) -> c_int
+where
+    F: FnOnce(&'static [&'static CharStar], &'static [&'static CharStar]) -> Fut,
+    Fut: 'static + Future,
+    Fut::Output: Terminate,
+{
+    use alloc::rc::Rc;
+    use core::cell::Cell;
+
+    let (args, env) = unsafe { make_slices(argv, envp) };
+
+    let exit_code = Rc::new(Cell::new(None));
+    let task_exit_code = exit_code.clone();
+    let main = f(args, env);
+
+    crate::runtime::spawn(async move {
+        task_exit_code.set(Some(main.await.terminate()));
+    });
+
+    let ret = loop {
+        if let Err(err) = crate::runtime::run_until_idle() {
+            crate::entry_point::report_error(&err);
+            break err.terminate();
+        }
+
+        if let Some(code) = exit_code.get() {
+            break code;
+        }
+
+        // Nothing will ever wake the main task up; waiting for it would spin forever.
+        if !crate::runtime::can_make_progress() {
+            crate::entry_point::report_error(&Errno::DEADLK);
+            break Errno::DEADLK.terminate();
+        }
+    };
+
+    // The main task is done; any remaining task is dropped with the runtime.
+    crate::runtime::clear();
+
+    ret
+}
+
+/// Turns the raw `argv` and `envp` arrays into slices, making them available through
+/// [`crate::env::args`] and [`crate::env::vars`].
+///
+/// # Safety
+///
+/// See [`call`].
+unsafe fn make_slices(
+    argv: *const *const c_char,
+    envp: *const *const c_char,
+) -> (&'static [&'static CharStar], &'static [&'static CharStar]) {
     unsafe {
         let argc = null_terminated_array_len(argv);
         let args = core::slice::from_raw_parts(argv as *const &CharStar, argc);
         let envc = null_terminated_array_len(envp);
         let env = core::slice::from_raw_parts(envp as *const &CharStar, envc);
-        f(args, env).terminate()
+        crate::env::set_globals(args, env);
+        (args, env)
     }
 }
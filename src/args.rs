@@ -0,0 +1,482 @@
+//! A small command-line argument parser with `getopt_long`-like semantics.
+//!
+//! # Syntax
+//!
+//! - Short options are a single dash followed by a character (`-v`). Multiple short options may
+//!   be clustered together (`-abc` is equivalent to `-a -b -c`).
+//!
+//! - Short options taking a value may receive it in the same argument (`-ofile`) or in the next
+//!   one (`-o file`).
+//!
+//! - Long options are two dashes followed by a name (`--verbose`). Their value may be provided
+//!   after an equal sign (`--out=file`) or in the next argument (`--out file`).
+//!
+//! - A lone `--` marks the end of options. Every argument after it is positional.
+//!
+//! - Any other argument, including a lone `-`, is positional.
+//!
+//! The parser never allocates: values and positional arguments borrow from `argv`.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use ft::args::{Arg, Opt, Parser};
+//!
+//! #[derive(Clone, Copy)]
+//! enum Flag {
+//!     Verbose,
+//!     Output,
+//! }
+//!
+//! const OPTS: &[Opt<Flag>] = &[
+//!     Opt::new(Flag::Verbose).short(b'v').long("verbose").help("print more information"),
+//!     Opt::new(Flag::Output).short(b'o').long("out").value("FILE").help("write to FILE"),
+//! ];
+//!
+//! let mut parser = Parser::new(OPTS, argv);
+//! while let Some(arg) = parser.next() {
+//!     match arg {
+//!         Ok(Arg::Opt(m)) => match m.id {
+//!             Flag::Verbose => verbose = true,
+//!             Flag::Output => output = m.value,
+//!         },
+//!         Ok(Arg::Positional(p)) => inputs += 1,
+//!         Err(err) => {
+//!             parser.print_error(&err);
+//!             parser.print_usage("[FILE]...");
+//!             return 2;
+//!         }
+//!     }
+//! }
+//! ```
+
+use core::fmt;
+
+use crate::{eprintf, CharStar};
+
+/// The description of an option accepted by a [`Parser`].
+///
+/// The `T` parameter is an identifier chosen by the user, returned by the parser when the option
+/// is encountered.
+#[derive(Debug, Clone, Copy)]
+pub struct Opt<T> {
+    /// The identifier of the option.
+    pub id: T,
+    /// The short name of the option (e.g. `b'v'` for `-v`).
+    pub short: Option<u8>,
+    /// The long name of the option (e.g. `"verbose"` for `--verbose`).
+    pub long: Option<&'static str>,
+    /// If the option takes a value, the name of that value in the usage message.
+    pub value: Option<&'static str>,
+    /// A short description of the option, displayed in the usage message.
+    pub help: &'static str,
+}
+
+impl<T> Opt<T> {
+    /// Creates a new [`Opt`] with no name and no value.
+    #[inline]
+    pub const fn new(id: T) -> Self {
+        Self {
+            id,
+            short: None,
+            long: None,
+            value: None,
+            help: "",
+        }
+    }
+
+    /// Sets the short name of the option.
+    #[inline]
+    pub const fn short(mut self, short: u8) -> Self {
+        self.short = Some(short);
+        self
+    }
+
+    /// Sets the long name of the option.
+    #[inline]
+    pub const fn long(mut self, long: &'static str) -> Self {
+        self.long = Some(long);
+        self
+    }
+
+    /// Makes the option take a value, using `name` to describe it in the usage message.
+    #[inline]
+    pub const fn value(mut self, name: &'static str) -> Self {
+        self.value = Some(name);
+        self
+    }
+
+    /// Sets the description of the option.
+    #[inline]
+    pub const fn help(mut self, help: &'static str) -> Self {
+        self.help = help;
+        self
+    }
+
+    /// Returns whether this option takes a value.
+    #[inline]
+    pub const fn takes_value(&self) -> bool {
+        self.value.is_some()
+    }
+}
+
+/// The name of an option, as written on the command-line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OptName<'a> {
+    /// A short option name (without the leading dash).
+    Short(u8),
+    /// A long option name (without the leading dashes).
+    Long(&'a [u8]),
+}
+
+impl fmt::Display for OptName<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Short(c) => write!(f, "-{}", crate::utils::display_bytes(&[c])),
+            Self::Long(name) => write!(f, "--{}", crate::utils::display_bytes(name)),
+        }
+    }
+}
+
+/// An option that was found on the command-line.
+#[derive(Debug, Clone, Copy)]
+pub struct Matched<'a, T> {
+    /// The identifier of the option.
+    pub id: T,
+    /// The name used to refer to the option.
+    pub name: OptName<'a>,
+    /// The value of the option, if it takes one.
+    pub value: Option<&'a CharStar>,
+}
+
+impl<'a, T> Matched<'a, T> {
+    /// Parses the value of this option.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the option has no value, or if the value cannot be parsed as a `V`.
+    pub fn parse<V: FromArg<'a>>(&self) -> Result<V, Error<'a>> {
+        let value = self.value.ok_or(Error::MissingValue(self.name))?;
+        V::from_arg(value).ok_or(Error::InvalidValue(self.name, value))
+    }
+}
+
+/// An argument returned by a [`Parser`].
+#[derive(Debug, Clone, Copy)]
+pub enum Arg<'a, T> {
+    /// An option.
+    Opt(Matched<'a, T>),
+    /// A positional argument.
+    Positional(&'a CharStar),
+}
+
+/// An error that might occur while parsing command-line arguments.
+#[derive(Debug, Clone, Copy)]
+pub enum Error<'a> {
+    /// The option is not known to the parser.
+    Unknown(OptName<'a>),
+    /// The option requires a value, but none was provided.
+    MissingValue(OptName<'a>),
+    /// The option does not take a value, but one was provided (`--flag=value`).
+    UnexpectedValue(OptName<'a>),
+    /// The value of the option could not be parsed.
+    InvalidValue(OptName<'a>, &'a CharStar),
+}
+
+impl fmt::Display for Error<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unknown(name) => write!(f, "unknown option '{name}'"),
+            Self::MissingValue(name) => write!(f, "option '{name}' requires a value"),
+            Self::UnexpectedValue(name) => write!(f, "option '{name}' does not take a value"),
+            Self::InvalidValue(name, value) => {
+                write!(f, "invalid value '{value}' for option '{name}'")
+            }
+        }
+    }
+}
+
+/// Types that can be parsed from a command-line argument.
+pub trait FromArg<'a>: Sized {
+    /// Parses the provided argument.
+    ///
+    /// If the argument is not valid, [`None`] is returned.
+    fn from_arg(arg: &'a CharStar) -> Option<Self>;
+}
+
+impl<'a> FromArg<'a> for &'a CharStar {
+    #[inline]
+    fn from_arg(arg: &'a CharStar) -> Option<Self> {
+        Some(arg)
+    }
+}
+
+impl<'a> FromArg<'a> for &'a str {
+    #[inline]
+    fn from_arg(arg: &'a CharStar) -> Option<Self> {
+        arg.as_str()
+    }
+}
+
+/// Implements [`FromArg`] for integer types.
+macro_rules! impl_FromArg_for_integers {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl FromArg<'_> for $t {
+                #[inline]
+                fn from_arg(arg: &CharStar) -> Option<Self> {
+                    arg.as_str()?.parse().ok()
+                }
+            }
+        )*
+    };
+}
+
+impl_FromArg_for_integers!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+/// Parses command-line arguments according to a list of [`Opt`]s.
+///
+/// The parser is an iterator over the arguments it finds.
+pub struct Parser<'a, T: 'static> {
+    /// The options accepted by the parser.
+    opts: &'static [Opt<T>],
+    /// The name of the program, used in messages.
+    program: &'a CharStar,
+    /// The arguments that remain to be parsed.
+    args: &'a [&'a CharStar],
+    /// The remaining part of a cluster of short options.
+    cluster: Option<&'a CharStar>,
+    /// Whether a `--` argument has been found.
+    only_positional: bool,
+}
+
+impl<'a, T: Copy> Parser<'a, T> {
+    /// Creates a new [`Parser`].
+    ///
+    /// The first element of `argv` is assumed to be the name of the program and is not parsed.
+    pub fn new(opts: &'static [Opt<T>], argv: &'a [&'a CharStar]) -> Self {
+        let (program, args) = match argv.split_first() {
+            Some((program, args)) => (*program, args),
+            None => (CharStar::EMPTY, argv),
+        };
+
+        Self {
+            opts,
+            program,
+            args,
+            cluster: None,
+            only_positional: false,
+        }
+    }
+
+    /// Returns the name of the program.
+    #[inline]
+    pub fn program(&self) -> &'a CharStar {
+        self.program
+    }
+
+    /// Returns the arguments that have not been parsed yet.
+    ///
+    /// This does not include the remaining part of a cluster of short options.
+    #[inline]
+    pub fn remaining(&self) -> &'a [&'a CharStar] {
+        self.args
+    }
+
+    /// Takes the next argument out of the list of arguments.
+    fn take_arg(&mut self) -> Option<&'a CharStar> {
+        let (first, rest) = self.args.split_first()?;
+        self.args = rest;
+        Some(first)
+    }
+
+    /// Parses a cluster of short options (without the leading dash).
+    ///
+    /// `cluster` must not be empty.
+    fn parse_short(&mut self, cluster: &'a CharStar) -> Result<Arg<'a, T>, Error<'a>> {
+        let (c, rest) = cluster.split_first().expect("empty option cluster");
+        let name = OptName::Short(c);
+
+        let Some(opt) = self.opts.iter().find(|opt| opt.short == Some(c)) else {
+            if !rest.is_empty() {
+                self.cluster = Some(rest);
+            }
+            return Err(Error::Unknown(name));
+        };
+
+        let value = if !opt.takes_value() {
+            if !rest.is_empty() {
+                self.cluster = Some(rest);
+            }
+            None
+        } else if !rest.is_empty() {
+            Some(rest)
+        } else {
+            Some(self.take_arg().ok_or(Error::MissingValue(name))?)
+        };
+
+        Ok(Arg::Opt(Matched {
+            id: opt.id,
+            name,
+            value,
+        }))
+    }
+
+    /// Parses a long option (without the leading dashes).
+    fn parse_long(&mut self, arg: &'a CharStar) -> Result<Arg<'a, T>, Error<'a>> {
+        let (name, inline_value) = match arg.split_at_char(b'=') {
+            Some((name, rest)) => (name, rest.strip_prefix(b"=")),
+            None => (arg.as_bytes(), None),
+        };
+
+        let Some(opt) = self
+            .opts
+            .iter()
+            .find(|opt| opt.long.is_some_and(|long| long.as_bytes() == name))
+        else {
+            return Err(Error::Unknown(OptName::Long(name)));
+        };
+
+        let name = OptName::Long(name);
+
+        let value = match (opt.takes_value(), inline_value) {
+            (false, None) => None,
+            (false, Some(_)) => return Err(Error::UnexpectedValue(name)),
+            (true, Some(value)) => Some(value),
+            (true, None) => Some(self.take_arg().ok_or(Error::MissingValue(name))?),
+        };
+
+        Ok(Arg::Opt(Matched {
+            id: opt.id,
+            name,
+            value,
+        }))
+    }
+
+    /// Prints the usage message of the program to the standard error stream.
+    ///
+    /// `operands` describes the positional arguments of the program (e.g. `"[FILE]..."`).
+    pub fn print_usage(&self, operands: &str) {
+        eprintf!("{}", Usage::new(self.program, self.opts, operands));
+    }
+
+    /// Prints the provided error to the standard error stream, prefixed with the name of the
+    /// program.
+    pub fn print_error(&self, err: &Error) {
+        eprintf!("{}: {}\n", self.program, err);
+    }
+}
+
+impl<'a, T: Copy> Iterator for Parser<'a, T> {
+    type Item = Result<Arg<'a, T>, Error<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(cluster) = self.cluster.take() {
+            return Some(self.parse_short(cluster));
+        }
+
+        let arg = self.take_arg()?;
+
+        if self.only_positional {
+            return Some(Ok(Arg::Positional(arg)));
+        }
+
+        if let Some(long) = arg.strip_prefix(b"--") {
+            if long.is_empty() {
+                self.only_positional = true;
+                return self.next();
+            }
+
+            return Some(self.parse_long(long));
+        }
+
+        match arg.strip_prefix(b"-") {
+            Some(cluster) if !cluster.is_empty() => Some(self.parse_short(cluster)),
+            _ => Some(Ok(Arg::Positional(arg))),
+        }
+    }
+}
+
+/// A usage message generated from a list of [`Opt`]s.
+///
+/// This type implements [`fmt::Display`].
+pub struct Usage<'a, T: 'static> {
+    /// The name of the program.
+    program: &'a CharStar,
+    /// The options accepted by the program.
+    opts: &'static [Opt<T>],
+    /// A description of the positional arguments.
+    operands: &'a str,
+}
+
+impl<'a, T> Usage<'a, T> {
+    /// Creates a new [`Usage`] instance.
+    #[inline]
+    pub fn new(program: &'a CharStar, opts: &'static [Opt<T>], operands: &'a str) -> Self {
+        Self {
+            program,
+            opts,
+            operands,
+        }
+    }
+}
+
+/// Returns the width of the left column of an option in the usage message.
+fn opt_width<T>(opt: &Opt<T>) -> usize {
+    // The short name always takes up 4 columns ("-v, "), even when it's missing.
+    let mut width = 4;
+    if let Some(long) = opt.long {
+        width += 2 + long.len();
+    }
+    if let Some(value) = opt.value {
+        width += 1 + value.len();
+    }
+    width
+}
+
+impl<T> fmt::Display for Usage<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "usage: {}", self.program)?;
+        if !self.opts.is_empty() {
+            f.write_str(" [OPTIONS]")?;
+        }
+        if !self.operands.is_empty() {
+            write!(f, " {}", self.operands)?;
+        }
+        f.write_str("\n")?;
+
+        if self.opts.is_empty() {
+            return Ok(());
+        }
+
+        f.write_str("\noptions:\n")?;
+
+        let column = self.opts.iter().map(opt_width).max().unwrap_or(0) + 2;
+
+        for opt in self.opts {
+            f.write_str("  ")?;
+
+            match (opt.short, opt.long) {
+                (Some(short), Some(_)) => write!(f, "{}, ", OptName::Short(short))?,
+                (Some(short), None) => write!(f, "{}  ", OptName::Short(short))?,
+                (None, _) => f.write_str("    ")?,
+            }
+
+            if let Some(long) = opt.long {
+                write!(f, "--{long}")?;
+            }
+
+            if let Some(value) = opt.value {
+                write!(f, " {value}")?;
+            }
+
+            if !opt.help.is_empty() {
+                write!(f, "{:1$}{2}", "", column - opt_width(opt), opt.help)?;
+            }
+
+            f.write_str("\n")?;
+        }
+
+        Ok(())
+    }
+}
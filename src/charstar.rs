@@ -394,6 +394,32 @@ impl CharStarBox {
             Ok(Self(NonNull::new_unchecked(p.as_ptr() as *mut CharStar)))
         }
     }
+
+    /// Concatenates the provided byte slices on the heap and returns a [`CharStarBox`] containing
+    /// the resulting string.
+    ///
+    /// Just like [`from_bytes`](Self::from_bytes), this function assumes that the provided
+    /// slices do not include any null byte.
+    pub fn concat(parts: &[&[u8]]) -> Result<Self, OutOfMemory> {
+        let len = parts
+            .iter()
+            .try_fold(0usize, |acc, part| acc.checked_add(part.len()))
+            .and_then(|len| len.checked_add(1))
+            .ok_or(OutOfMemory)?;
+
+        let p = crate::malloc::allocate(len)?;
+
+        unsafe {
+            let mut cur = p.as_ptr().cast::<u8>();
+            for part in parts {
+                cur.copy_from(part.as_ptr(), part.len());
+                cur = cur.add(part.len());
+            }
+            cur.write(0);
+
+            Ok(Self(NonNull::new_unchecked(p.as_ptr() as *mut CharStar)))
+        }
+    }
 }
 
 impl Clone for CharStarBox {
@@ -2,6 +2,8 @@
 
 pub mod array_vec;
 pub mod read_buffer;
+pub mod write_buffer;
 
 pub use self::array_vec::ArrayVec;
 pub use self::read_buffer::ReadBuffer;
+pub use self::write_buffer::WriteBuffer;
@@ -3,8 +3,12 @@
 use core::mem::MaybeUninit;
 use core::ptr::NonNull;
 
+use crate::errno::retry_on_interrupt;
 #[cfg(feature = "futures")]
 use crate::futures;
+#[cfg(feature = "futures")]
+use crate::io::AsyncRead;
+use crate::io::Read;
 use crate::malloc::OutOfMemory;
 use crate::{Errno, Fd, MemchrExt};
 
@@ -247,8 +251,14 @@ impl ReadBuffer {
     /// that at least *some* additional space is available.
     ///
     /// [`reserve`]: ReadBuffer::reserve
+    #[inline]
     pub fn fill_with_fd(&mut self, fd: Fd) -> Result<usize, Errno> {
-        let count = fd.read(self.spare_capacity_mut())?;
+        self.fill_from(fd)
+    }
+
+    /// Like [`fill_with_fd`](Self::fill_with_fd), but reads from any [`Read`] implementation.
+    pub fn fill_from<R: Read>(&mut self, mut reader: R) -> Result<usize, Errno> {
+        let count = reader.read(self.spare_capacity_mut())?;
 
         unsafe {
             self.assume_init(count);
@@ -265,7 +275,20 @@ impl ReadBuffer {
         futures::FillWithFd { buf: self, fd }
     }
 
-    /// Reads from the file descriptor until the given delimiter is found.
+    /// Like [`fill_from`](Self::fill_from), but asynchronous.
+    #[inline]
+    #[cfg(feature = "futures")]
+    pub fn async_fill_from<'a, R>(&'a mut self, reader: R) -> futures::FillWithFd<'a, R>
+    where
+        R: AsyncRead + Unpin,
+    {
+        futures::FillWithFd {
+            buf: self,
+            fd: reader,
+        }
+    }
+
+    /// Reads from `reader` until the given delimiter is found.
     ///
     /// The delimiter is included in the buffer.
     ///
@@ -275,9 +298,16 @@ impl ReadBuffer {
     ///
     /// The current pending buffer is checked. If a delimiter is found in the current pending
     /// buffer, it will be immediately returned. The returned part of the buffer will be consumed
-    /// and the next call to `read_until` will continue reading from the file descriptor (or parsing
+    /// and the next call to `read_until` will continue reading from the reader (or parsing
     /// the existing pending buffer).
-    pub fn read_until(&mut self, fd: Fd, delimiter: &[u8]) -> Result<&mut [u8], Errno> {
+    ///
+    /// If the reader is exhausted before the delimiter is found, the whole pending buffer is
+    /// returned instead. It does not end with the delimiter, and may be empty.
+    pub fn read_until<R: Read>(
+        &mut self,
+        mut reader: R,
+        delimiter: &[u8],
+    ) -> Result<&mut [u8], Errno> {
         if delimiter.is_empty() {
             return Ok(&mut []);
         }
@@ -294,52 +324,72 @@ impl ReadBuffer {
             }
 
             self.reserve(batch_size)?;
-            self.fill_with_fd(fd)?;
+            if retry_on_interrupt(|| self.fill_from(&mut reader))? == 0 {
+                let len = self.pending().len();
+                return unsafe { Ok(self.consume_unchecked(len)) };
+            }
 
             batch_size = batch_size.saturating_mul(2);
         }
     }
 
-    /// Reads exactly `count` bytes from the file descriptor (or from the pending buffer if it is
-    /// not empty).
+    /// Reads exactly `count` bytes from `reader` (or from the pending buffer if it is not
+    /// empty).
     ///
     /// # Remarks
     ///
     /// The current pending buffer is checked. If it contains at least `count` bytes, they will be
     /// immediately returned. The returned part of the buffer will be consumed and the next call to
-    /// `read_exact` will continue reading from the file descriptor (or parsing the existing pending
+    /// `read_exact` will continue reading from the reader (or parsing the existing pending
     /// buffer).
-    pub fn read_exact(&mut self, fd: Fd, count: usize) -> Result<&mut [u8], Errno> {
+    ///
+    /// # Errors
+    ///
+    /// If the reader is exhausted before `count` bytes are available, this function fails with
+    /// [`Errno::NODATA`]. The data that could be read remains in the pending buffer.
+    pub fn read_exact<R: Read>(&mut self, mut reader: R, count: usize) -> Result<&mut [u8], Errno> {
         loop {
             if self.pending().len() >= count {
                 return unsafe { Ok(self.consume_unchecked(count)) };
             }
 
             self.reserve(count.saturating_sub(self.pending().len()))?;
-            self.fill_with_fd(fd)?;
+            if retry_on_interrupt(|| self.fill_from(&mut reader))? == 0 {
+                return Err(Errno::NODATA);
+            }
         }
     }
 
     /// Like [`read_until`](Self::read_until), but asynchronous.
     ///
-    /// This function assumes that the provided file descriptor is non-blocking.
+    /// This function assumes that the provided reader is non-blocking.
     #[inline]
     #[cfg(feature = "futures")]
-    pub fn async_read_until<'a, 'd>(
+    pub fn async_read_until<'a, 'd, R>(
         &'a mut self,
-        fd: Fd,
+        reader: R,
         delimiter: &'d [u8],
-    ) -> futures::ReadUntil<'a, 'd> {
-        futures::ReadUntil::new(fd, self, delimiter)
+    ) -> futures::ReadUntil<'a, 'd, R>
+    where
+        R: AsyncRead + Unpin,
+    {
+        futures::ReadUntil::new(reader, self, delimiter)
     }
 
     /// Like [`read_exact`](Self::read_exact), but asynchronous.
     ///
-    /// This function assumes that the provided file descriptor is non-blocking.
+    /// This function assumes that the provided reader is non-blocking.
     #[inline]
     #[cfg(feature = "futures")]
-    pub fn async_read_exact<'a>(&'a mut self, fd: Fd, count: usize) -> futures::ReadExact<'a> {
-        futures::ReadExact::new(fd, self, count)
+    pub fn async_read_exact<'a, R>(
+        &'a mut self,
+        reader: R,
+        count: usize,
+    ) -> futures::ReadExact<'a, R>
+    where
+        R: AsyncRead + Unpin,
+    {
+        futures::ReadExact::new(reader, self, count)
     }
 }
 
@@ -0,0 +1,311 @@
+//! Defines the [`WriteBuffer`] collection.
+
+use core::fmt;
+use core::ptr::NonNull;
+
+#[cfg(feature = "futures")]
+use crate::futures;
+use crate::malloc::OutOfMemory;
+use crate::{Errno, Fd, MemchrExt};
+
+/// A buffer that accumulates data before writing it to a file descriptor in large chunks.
+///
+/// Data is written to the file descriptor when the buffer is full, when [`flush`] is called,
+/// and when the buffer is dropped. In line-buffered mode, the buffer is also flushed every time
+/// a newline character is written, which is usually what one wants for terminals.
+///
+/// # Representation
+///
+/// ```text
+/// +-------------------+-------------------+-------------------+
+/// | Flushed           | Pending           | Free              |
+/// +-------------------+-------------------+-------------------+
+/// ^                   ^                   ^                   ^
+/// data                start               end                 capacity
+/// ```
+///
+/// The **flushed** part is only non-empty when a flush was interrupted after a partial write,
+/// which typically happens with non-blocking file descriptors.
+///
+/// [`flush`]: WriteBuffer::flush
+pub struct WriteBuffer {
+    /// The file descriptor the data is written to.
+    fd: Fd,
+    /// The data buffer.
+    data: NonNull<u8>,
+    /// The total capacity of the data buffer.
+    capacity: usize,
+    /// The start of the data that has not been written to the file descriptor yet.
+    start: usize,
+    /// The end of the data that has not been written to the file descriptor yet.
+    end: usize,
+    /// Whether the buffer is flushed after every newline character.
+    line_buffered: bool,
+}
+
+unsafe impl Send for WriteBuffer {}
+unsafe impl Sync for WriteBuffer {}
+
+impl WriteBuffer {
+    /// The capacity used by [`for_fd`](Self::for_fd).
+    pub const DEFAULT_CAPACITY: usize = 4096;
+
+    /// Creates a new [`WriteBuffer`] that writes to `fd` and can hold up to `capacity` bytes.
+    ///
+    /// The buffer is not line-buffered.
+    pub fn with_capacity(fd: Fd, capacity: usize) -> Result<Self, OutOfMemory> {
+        let (data, capacity) = if capacity == 0 {
+            (NonNull::dangling(), 0)
+        } else {
+            let data = crate::malloc::allocate(capacity)?;
+            (data.as_non_null_ptr(), data.len())
+        };
+
+        Ok(Self {
+            fd,
+            data,
+            capacity,
+            start: 0,
+            end: 0,
+            line_buffered: false,
+        })
+    }
+
+    /// Creates a new [`WriteBuffer`] that writes to `fd`, with a capacity of
+    /// [`DEFAULT_CAPACITY`](Self::DEFAULT_CAPACITY) bytes.
+    ///
+    /// The buffer is line-buffered if `fd` refers to a terminal.
+    pub fn for_fd(fd: Fd) -> Result<Self, OutOfMemory> {
+        let mut buf = Self::with_capacity(fd, Self::DEFAULT_CAPACITY)?;
+        buf.line_buffered = fd.is_a_tty();
+        Ok(buf)
+    }
+
+    /// Returns the file descriptor this buffer writes to.
+    #[inline]
+    pub fn fd(&self) -> Fd {
+        self.fd
+    }
+
+    /// Returns the capacity of the buffer.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns whether the buffer is flushed after every newline character.
+    #[inline]
+    pub fn is_line_buffered(&self) -> bool {
+        self.line_buffered
+    }
+
+    /// Sets whether the buffer should be flushed after every newline character.
+    #[inline]
+    pub fn set_line_buffered(&mut self, yes: bool) {
+        self.line_buffered = yes;
+    }
+
+    /// Returns the data that has been written to the buffer but not yet to the file descriptor.
+    #[inline]
+    pub fn pending(&self) -> &[u8] {
+        unsafe {
+            core::slice::from_raw_parts(self.data.as_ptr().add(self.start), self.end - self.start)
+        }
+    }
+
+    /// Marks the first `count` pending bytes as written to the file descriptor.
+    #[inline]
+    fn consume(&mut self, count: usize) {
+        debug_assert!(self.start + count <= self.end);
+        self.start += count;
+
+        if self.start == self.end {
+            self.start = 0;
+            self.end = 0;
+        }
+    }
+
+    /// Writes all the pending data to the file descriptor.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs, the data that could not be written remains in the buffer, and the
+    /// next flush will attempt to write it again.
+    pub fn flush(&mut self) -> Result<(), Errno> {
+        while !self.pending().is_empty() {
+            let count = self.fd.write(self.pending())?;
+            self.consume(count);
+        }
+
+        Ok(())
+    }
+
+    /// Like [`flush`](Self::flush), but asynchronous.
+    ///
+    /// This function assumes that the file descriptor is non-blocking.
+    #[inline]
+    #[cfg(feature = "futures")]
+    pub fn async_flush(&mut self) -> futures::Flush<'_> {
+        futures::Flush { buf: self }
+    }
+
+    /// Attempts to write pending data to the file descriptor.
+    ///
+    /// If the operation would block, this function returns `Pending` and schedules the current
+    /// task to be woken up when the file descriptor is ready for writing.
+    #[cfg(feature = "rt-single-thread")]
+    pub fn poll_flush(
+        &mut self,
+        cx: &mut core::task::Context,
+    ) -> core::task::Poll<Result<(), Errno>> {
+        while !self.pending().is_empty() {
+            match core::task::ready!(self.fd.poll_write(self.pending(), cx)) {
+                Ok(count) => self.consume(count),
+                Err(err) => return core::task::Poll::Ready(Err(err)),
+            }
+        }
+
+        core::task::Poll::Ready(Ok(()))
+    }
+
+    /// Writes the provided data to the buffer, flushing it if needed.
+    ///
+    /// Data that is at least as large as the capacity of the buffer is written directly to the
+    /// file descriptor.
+    ///
+    /// In line-buffered mode, the buffer is flushed when `data` contains a newline character.
+    /// If that flush fails, `data` still remains in the buffer, and the error is reported by the
+    /// next call to [`flush`](Self::flush) instead.
+    ///
+    /// # Returns
+    ///
+    /// The number of bytes of `data` that were accepted, either by the buffer or by the file
+    /// descriptor. This is always `data.len()`, unless `data` is written directly and the
+    /// file descriptor only accepts part of it (which typically happens with non-blocking file
+    /// descriptors).
+    ///
+    /// # Errors
+    ///
+    /// If the buffer has to be flushed and flushing fails, or if writing directly to the file
+    /// descriptor fails before anything could be written, the error is returned and `data` is
+    /// not written at all. With non-blocking file descriptors, this typically means that
+    /// [`async_flush`](Self::async_flush) must be awaited before trying again.
+    pub fn write(&mut self, data: &[u8]) -> Result<usize, Errno> {
+        if data.len() > self.capacity - self.end {
+            self.flush()?;
+        }
+
+        if data.len() >= self.capacity {
+            return self.write_direct(data);
+        }
+
+        unsafe {
+            self.data
+                .as_ptr()
+                .add(self.end)
+                .copy_from_nonoverlapping(data.as_ptr(), data.len());
+        }
+        self.end += data.len();
+
+        if self.line_buffered && data.memchr(b'\n').is_some() {
+            // The data has been accepted, so the error must not be reported here: the caller
+            // would write it again.
+            let _ = self.flush();
+        }
+
+        Ok(data.len())
+    }
+
+    /// Writes `data` directly to the file descriptor, bypassing the buffer.
+    ///
+    /// # Returns
+    ///
+    /// The number of bytes written. Errors are only returned when nothing could be written.
+    fn write_direct(&mut self, data: &[u8]) -> Result<usize, Errno> {
+        let mut written = 0;
+
+        while written < data.len() {
+            match self.fd.write(&data[written..]) {
+                Ok(0) if written == 0 => return Err(Errno::NOSPC),
+                Ok(0) => break,
+                Ok(count) => written += count,
+                Err(err) if written == 0 => return Err(err),
+                Err(_) => break,
+            }
+        }
+
+        Ok(written)
+    }
+
+    /// Writes the provided arguments to the buffer.
+    ///
+    /// Unlike the [`fmt::Write`] implementation, this function preserves the I/O error that
+    /// might occur, allowing the [`write!`] macro to return an [`Errno`].
+    pub fn write_fmt(&mut self, args: fmt::Arguments) -> Result<(), Errno> {
+        /// An adapter that keeps track of I/O errors instead of discarding them.
+        struct Adapter<'a> {
+            /// The buffer to write to.
+            buf: &'a mut WriteBuffer,
+            /// An error that might have occurred while writing.
+            err: Errno,
+        }
+
+        impl fmt::Write for Adapter<'_> {
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                crate::io::Write::write_all(self.buf, s.as_bytes()).map_err(|err| {
+                    self.err = err;
+                    fmt::Error
+                })
+            }
+        }
+
+        let mut output = Adapter {
+            buf: self,
+            err: Errno::SUCCESS,
+        };
+
+        fmt::write(&mut output, args).map_err(|_| output.err)
+    }
+}
+
+impl fmt::Write for WriteBuffer {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        crate::io::Write::write_all(self, s.as_bytes()).map_err(|_| fmt::Error)
+    }
+}
+
+impl crate::io::Write for WriteBuffer {
+    #[inline]
+    fn write(&mut self, data: &[u8]) -> Result<usize, Errno> {
+        WriteBuffer::write(self, data)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<(), Errno> {
+        WriteBuffer::flush(self)
+    }
+}
+
+impl fmt::Debug for WriteBuffer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WriteBuffer")
+            .field("fd", &self.fd)
+            .field("capacity", &self.capacity)
+            .field("pending", &self.pending().len())
+            .field("line_buffered", &self.line_buffered)
+            .finish()
+    }
+}
+
+impl Drop for WriteBuffer {
+    fn drop(&mut self) {
+        let _ = self.flush();
+
+        if self.capacity != 0 {
+            unsafe {
+                crate::malloc::deallocate(self.data);
+            }
+        }
+    }
+}
@@ -1,6 +1,11 @@
 //! Provides way to safely declare an entry point for the program.
 
 use core::ffi::c_int;
+use core::fmt;
+use core::sync::atomic::AtomicU8;
+use core::sync::atomic::Ordering::Relaxed;
+
+use crate::Errno;
 
 /// Types that can be used as the output of the `main` function.
 pub trait Terminate {
@@ -29,18 +34,187 @@ impl Terminate for () {
     }
 }
 
-impl<A, B> Terminate for Result<A, B>
+/// Returns an error to the caller of `main`.
+///
+/// The error is printed to the standard error stream as `<program>: <error>`, and the program
+/// exits with the code configured by [`set_failure_code`] ([`ExitCode::FAILURE`] by default).
+///
+/// # Notes
+///
+/// Integers are errors like any other: `Err(3)` prints `<program>: 3` and exits with the failure
+/// code, not with `3`. Return `Err(ExitCode::from_raw(3))` to exit with a specific code without
+/// printing anything.
+impl<T, E> Terminate for Result<T, E>
 where
-    A: Terminate,
-    B: Terminate,
+    T: Terminate,
+    E: fmt::Display,
+{
+    fn terminate(self) -> c_int {
+        match self {
+            Ok(ok) => ok.terminate(),
+            Err(err) => {
+                report_error(&err);
+                failure_code().terminate()
+            }
+        }
+    }
+}
+
+/// Returns an error to the caller of `main`, assuming that it has already been reported.
+///
+/// Nothing is printed, and the program exits with the provided [`ExitCode`].
+impl<T> Terminate for Result<T, ExitCode>
+where
+    T: Terminate,
 {
     #[inline]
     fn terminate(self) -> c_int {
         match self {
             Ok(ok) => ok.terminate(),
-            Err(err) => err.terminate(),
+            Err(code) => code.terminate(),
+        }
+    }
+}
+
+/// Maps the error to an exit code, as described in [`ExitCode::from_errno`].
+///
+/// Nothing is printed.
+impl Terminate for Errno {
+    #[inline]
+    fn terminate(self) -> c_int {
+        ExitCode::from_errno(self).terminate()
+    }
+}
+
+/// Prints the provided error to the standard error stream, prefixed with the name of the
+/// program.
+pub fn report_error(err: &dyn fmt::Display) {
+    let program = crate::env::program_name();
+
+    if program.is_empty() {
+        crate::eprintf!("{err}\n");
+    } else {
+        crate::eprintf!("{program}: {err}\n");
+    }
+}
+
+/// The exit code used when `main` returns an error.
+static FAILURE_CODE: AtomicU8 = AtomicU8::new(ExitCode::FAILURE.to_raw());
+
+/// Sets the exit code used when `main` returns an error.
+///
+/// By default, this is [`ExitCode::FAILURE`].
+#[inline]
+pub fn set_failure_code(code: ExitCode) {
+    FAILURE_CODE.store(code.to_raw(), Relaxed);
+}
+
+/// Returns the exit code used when `main` returns an error.
+#[inline]
+pub fn failure_code() -> ExitCode {
+    ExitCode::from_raw(FAILURE_CODE.load(Relaxed))
+}
+
+/// The exit status of a program.
+///
+/// Apart from [`SUCCESS`](Self::SUCCESS) and [`FAILURE`](Self::FAILURE), the constants of
+/// this type come from `sysexits.h`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ExitCode(u8);
+
+impl ExitCode {
+    /// The program completed successfully.
+    pub const SUCCESS: Self = Self(0);
+    /// The program failed.
+    pub const FAILURE: Self = Self(1);
+    /// The command was used incorrectly.
+    pub const USAGE: Self = Self(64);
+    /// The input data was incorrect.
+    pub const DATAERR: Self = Self(65);
+    /// An input file did not exist or was not readable.
+    pub const NOINPUT: Self = Self(66);
+    /// The specified user did not exist.
+    pub const NOUSER: Self = Self(67);
+    /// The specified host did not exist.
+    pub const NOHOST: Self = Self(68);
+    /// A service is unavailable.
+    pub const UNAVAILABLE: Self = Self(69);
+    /// An internal software error has been detected.
+    pub const SOFTWARE: Self = Self(70);
+    /// An operating system error has been detected.
+    pub const OSERR: Self = Self(71);
+    /// A system file does not exist, cannot be opened, or has some sort of error.
+    pub const OSFILE: Self = Self(72);
+    /// A user-specified output file cannot be created.
+    pub const CANTCREAT: Self = Self(73);
+    /// An error occurred while doing I/O on some file.
+    pub const IOERR: Self = Self(74);
+    /// A temporary failure occurred. The user is invited to retry later.
+    pub const TEMPFAIL: Self = Self(75);
+    /// The remote system returned something that was not possible during a protocol exchange.
+    pub const PROTOCOL: Self = Self(76);
+    /// The user did not have sufficient permissions to perform the operation.
+    pub const NOPERM: Self = Self(77);
+    /// Something was found in an unconfigured or misconfigured state.
+    pub const CONFIG: Self = Self(78);
+
+    /// Creates a new [`ExitCode`] from the provided raw value.
+    #[inline]
+    pub const fn from_raw(code: u8) -> Self {
+        Self(code)
+    }
+
+    /// Returns the raw value of this [`ExitCode`].
+    #[inline]
+    pub const fn to_raw(self) -> u8 {
+        self.0
+    }
+
+    /// Returns the exit code that best describes the provided [`Errno`].
+    ///
+    /// Errors that have no obvious equivalent map to [`ExitCode::FAILURE`].
+    pub const fn from_errno(errno: Errno) -> Self {
+        match errno {
+            Errno::SUCCESS => Self::SUCCESS,
+            Errno::NOENT | Errno::NOTDIR => Self::NOINPUT,
+            Errno::ACCES | Errno::PERM => Self::NOPERM,
+            Errno::INVAL => Self::USAGE,
+            Errno::IO => Self::IOERR,
+            Errno::NOMEM | Errno::MFILE | Errno::NFILE | Errno::CHILD => Self::OSERR,
+            Errno::AGAIN | Errno::INTR | Errno::BUSY => Self::TEMPFAIL,
+            Errno::EXIST | Errno::ROFS | Errno::NOSPC | Errno::ISDIR => Self::CANTCREAT,
+            Errno::HOSTUNREACH | Errno::NETUNREACH | Errno::CONNREFUSED => Self::UNAVAILABLE,
+            Errno::PROTO => Self::PROTOCOL,
+            _ => Self::FAILURE,
         }
     }
+
+    /// Returns whether this [`ExitCode`] indicates success.
+    #[inline]
+    pub const fn is_success(self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl Terminate for ExitCode {
+    #[inline]
+    fn terminate(self) -> c_int {
+        self.0 as c_int
+    }
+}
+
+impl From<u8> for ExitCode {
+    #[inline]
+    fn from(code: u8) -> Self {
+        Self(code)
+    }
+}
+
+impl From<Errno> for ExitCode {
+    #[inline]
+    fn from(errno: Errno) -> Self {
+        Self::from_errno(errno)
+    }
 }
 
 /// Declares an entry point for the program.
@@ -61,7 +235,24 @@ where
 /// to the program.
 ///
 /// The return value of the entry point function can be anything that implements the [`Terminate`]
-/// trait.
+/// trait. Notably, returning `Err(err)` where `err` implements [`Display`](fmt::Display) prints
+/// `<program>: <err>` to the standard error stream before exiting with a failure code.
+///
+/// This applies to integer errors as well, so a `main` that used to return `Err(code)` with
+/// `code` a `u8` or a `c_int` to pick its exit code should return a `Result<_, ExitCode>`
+/// instead, which exits with the provided [`ExitCode`] without printing anything.
+///
+/// # Async Entry Points
+///
+/// When the `rt-single-thread` feature is enabled, the function may be prefixed with the `async`
+/// keyword. In that case, it must be an `async fn` taking the same arguments, and its output must
+/// implement [`Terminate`]. The returned future is spawned on the runtime, which is then driven
+/// until that future completes. Tasks that are still pending at that point are dropped.
+///
+/// If the runtime fails, an error message is printed to the standard error stream and the program
+/// exits with the [`ExitCode`] associated with the error. The same happens with
+/// [`Errno::DEADLK`] when the future can never complete, because no task is ready to run and
+/// none is waiting for I/O or for a timer.
 ///
 /// # Examples
 ///
@@ -76,9 +267,33 @@ where
 ///     0
 /// }
 /// ```
+///
+/// ```ignore
+/// # use ft::entry_point;
+/// # use ft::CharStar;
+/// #
+/// entry_point!(async main);
+///
+/// async fn main(argv: &[&CharStar], envp: &[&CharStar]) -> u8 {
+///     // ...
+///     0
+/// }
+/// ```
 #[macro_export]
 #[doc(alias = "main")]
 macro_rules! entry_point {
+    (async $f:expr) => {
+        const _: () = {
+            #[export_name = "main"]
+            pub extern "C" fn __libft_main(
+                _argc: ::core::ffi::c_int,
+                argv: *const *const ::core::ffi::c_char,
+                envp: *const *const ::core::ffi::c_char,
+            ) -> ::core::ffi::c_int {
+                unsafe { $crate::__private::entry_point::call_async($f, argv, envp) }
+            }
+        };
+    };
     ($f:expr) => {
         const _: () = {
             #[export_name = "main"]
@@ -0,0 +1,359 @@
+//! Provides ways to inspect and build the environment of a process.
+//!
+//! # Process-global arguments
+//!
+//! The [`args`], [`vars`] and [`program_name`] functions give access to the `argv` and `envp`
+//! arrays of the current process from anywhere in the program. They are populated at startup,
+//! either by the [`entry_point!`](crate::entry_point) macro, or through the `.init_array`
+//! section on Linux (glibc passes `argc`, `argv` and `envp` to those constructors).
+//!
+//! Before being populated, these functions behave as if the process had no arguments and an
+//! empty environment.
+
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+use core::ffi::{c_char, c_int};
+use core::iter::FusedIterator;
+use core::sync::atomic::Ordering::{Acquire, Relaxed, Release};
+use core::sync::atomic::{AtomicPtr, AtomicUsize};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "alloc")]
+use crate::charstar::CharStarBox;
+use crate::CharStar;
+#[cfg(feature = "alloc")]
+use crate::{Errno, Result};
+
+/// Splits an environment entry into its key and its value.
+///
+/// Entries that do not contain any `=` sign are considered to have an empty value.
+fn split_entry(entry: &CharStar) -> (&[u8], &CharStar) {
+    match entry.split_at_char(b'=') {
+        Some((key, rest)) => (key, unsafe { rest.split_at_unchecked(1).1 }),
+        None => (entry.as_bytes(), CharStar::EMPTY),
+    }
+}
+
+/// A view over a list of environment variables, such as the `envp` array passed to the entry
+/// point of the program.
+///
+/// Each entry is expected to be of the form `KEY=VALUE`.
+#[derive(Debug, Clone, Copy)]
+pub struct Env<'a>(&'a [&'a CharStar]);
+
+impl<'a> Env<'a> {
+    /// Creates a new [`Env`] instance from the provided list of `KEY=VALUE` entries.
+    #[inline]
+    pub const fn new(entries: &'a [&'a CharStar]) -> Self {
+        Self(entries)
+    }
+
+    /// Returns the raw `KEY=VALUE` entries of the environment.
+    #[inline]
+    pub const fn as_slice(&self) -> &'a [&'a CharStar] {
+        self.0
+    }
+
+    /// Returns the value of the variable named `key`.
+    ///
+    /// If the variable is defined multiple times, the first definition is returned.
+    #[doc(alias = "getenv")]
+    pub fn get(&self, key: &[u8]) -> Option<&'a CharStar> {
+        self.0.iter().find_map(|entry| {
+            let rest = entry.strip_prefix(key)?;
+            let (b'=', value) = rest.split_first()? else {
+                return None;
+            };
+            Some(value)
+        })
+    }
+
+    /// Returns whether the variable named `key` is defined.
+    #[inline]
+    pub fn contains(&self, key: &[u8]) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Returns an iterator over the `(key, value)` pairs of the environment.
+    #[inline]
+    pub fn iter(&self) -> EnvIter<'a> {
+        EnvIter(self.0.iter())
+    }
+}
+
+impl<'a> IntoIterator for Env<'a> {
+    type Item = (&'a [u8], &'a CharStar);
+    type IntoIter = EnvIter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the `(key, value)` pairs of an [`Env`].
+#[derive(Debug, Clone)]
+pub struct EnvIter<'a>(core::slice::Iter<'a, &'a CharStar>);
+
+impl<'a> Iterator for EnvIter<'a> {
+    type Item = (&'a [u8], &'a CharStar);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|entry| split_entry(entry))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for EnvIter<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back().map(|entry| split_entry(entry))
+    }
+}
+
+impl ExactSizeIterator for EnvIter<'_> {}
+impl FusedIterator for EnvIter<'_> {}
+
+/// An owned and mutable list of environment variables.
+///
+/// This type can produce a null-terminated `envp` array suitable for [`exec`].
+///
+/// [`exec`]: crate::process::exec
+#[cfg(feature = "alloc")]
+#[derive(Debug, Default)]
+pub struct EnvBuilder {
+    /// The `KEY=VALUE` entries of the environment.
+    ///
+    /// When the list is not empty, its last element is always `None`, making the vector a valid
+    /// null-terminated array of strings.
+    entries: Vec<Option<CharStarBox>>,
+}
+
+#[cfg(feature = "alloc")]
+impl EnvBuilder {
+    /// Creates a new, empty, [`EnvBuilder`].
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+
+    /// Creates a new [`EnvBuilder`] by copying the entries of the provided [`Env`].
+    pub fn from_env(env: Env) -> Result<Self> {
+        let mut entries = Vec::new();
+        entries
+            .try_reserve_exact(env.as_slice().len() + 1)
+            .map_err(|_| Errno::NOMEM)?;
+
+        for entry in env.as_slice() {
+            entries.push(Some(CharStarBox::from_bytes(entry.as_bytes())?));
+        }
+        entries.push(None);
+
+        Ok(Self { entries })
+    }
+
+    /// Returns the number of entries that are not the terminating null pointer.
+    #[inline]
+    fn len(&self) -> usize {
+        self.entries.len().saturating_sub(1)
+    }
+
+    /// Returns a view over the entries of this [`EnvBuilder`].
+    #[inline]
+    pub fn as_env(&self) -> Env<'_> {
+        // SAFETY:
+        //  `Option<CharStarBox>` has the same layout as `&CharStar`, and none of the first
+        //  `len` entries are `None`.
+        let entries = unsafe {
+            core::slice::from_raw_parts(self.entries.as_ptr() as *const &CharStar, self.len())
+        };
+
+        Env::new(entries)
+    }
+
+    /// Returns the value of the variable named `key`.
+    #[inline]
+    #[doc(alias = "getenv")]
+    pub fn get(&self, key: &[u8]) -> Option<&CharStar> {
+        self.as_env().get(key)
+    }
+
+    /// Returns the index of the variable named `key`.
+    fn position(&self, key: &[u8]) -> Option<usize> {
+        self.as_env()
+            .iter()
+            .position(|(entry_key, _)| entry_key == key)
+    }
+
+    /// Sets the value of the variable named `key`, replacing any previous value.
+    ///
+    /// # Errors
+    ///
+    /// This function fails with [`Errno::INVAL`] if `key` is empty or contains an `=` sign, and
+    /// with [`Errno::NOMEM`] if the system is out of memory.
+    #[doc(alias = "setenv")]
+    pub fn set(&mut self, key: &[u8], value: &[u8]) -> Result<()> {
+        if key.is_empty() || key.contains(&b'=') {
+            return Err(Errno::INVAL);
+        }
+
+        let entry = CharStarBox::concat(&[key, b"=", value])?;
+
+        match self.position(key) {
+            Some(index) => self.entries[index] = Some(entry),
+            None => {
+                let len = self.len();
+                self.entries.try_reserve(2).map_err(|_| Errno::NOMEM)?;
+                self.entries.truncate(len);
+                self.entries.push(Some(entry));
+                self.entries.push(None);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Removes the variable named `key`.
+    ///
+    /// # Returns
+    ///
+    /// Whether the variable was defined.
+    #[doc(alias = "unsetenv")]
+    pub fn unset(&mut self, key: &[u8]) -> bool {
+        match self.position(key) {
+            Some(index) => {
+                self.entries.remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes all the variables.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Returns a null-terminated array of null-terminated `KEY=VALUE` strings, suitable for
+    /// [`exec`].
+    ///
+    /// The returned pointer remains valid until this [`EnvBuilder`] is modified or dropped.
+    ///
+    /// [`exec`]: crate::process::exec
+    pub fn as_envp(&self) -> *const *const CharStar {
+        /// An empty null-terminated array, used when no variables are defined.
+        static EMPTY: [Option<&CharStar>; 1] = [None];
+
+        if self.entries.is_empty() {
+            EMPTY.as_ptr() as *const *const CharStar
+        } else {
+            self.entries.as_ptr() as *const *const CharStar
+        }
+    }
+}
+
+/// A slice of strings stored in a global variable.
+struct GlobalSlice {
+    /// A pointer to the first element of the slice.
+    ///
+    /// When the slice has not been set yet, this is null.
+    ptr: AtomicPtr<&'static CharStar>,
+    /// The number of elements in the slice.
+    len: AtomicUsize,
+}
+
+impl GlobalSlice {
+    /// Creates a new, unset, [`GlobalSlice`].
+    const fn new() -> Self {
+        Self {
+            ptr: AtomicPtr::new(core::ptr::null_mut()),
+            len: AtomicUsize::new(0),
+        }
+    }
+
+    /// Sets the value of the slice.
+    fn set(&self, slice: &'static [&'static CharStar]) {
+        self.len.store(slice.len(), Relaxed);
+        self.ptr.store(slice.as_ptr().cast_mut(), Release);
+    }
+
+    /// Returns the value of the slice, or an empty slice if it has not been set.
+    fn get(&self) -> &'static [&'static CharStar] {
+        let ptr = self.ptr.load(Acquire);
+
+        if ptr.is_null() {
+            &[]
+        } else {
+            unsafe { core::slice::from_raw_parts(ptr, self.len.load(Relaxed)) }
+        }
+    }
+}
+
+/// The `argv` array of the current process.
+static ARGS: GlobalSlice = GlobalSlice::new();
+
+/// The `envp` array of the current process.
+static VARS: GlobalSlice = GlobalSlice::new();
+
+/// Stores the provided `argv` and `envp` arrays as the process-global ones.
+pub(crate) fn set_globals(args: &'static [&'static CharStar], vars: &'static [&'static CharStar]) {
+    ARGS.set(args);
+    VARS.set(vars);
+}
+
+/// Returns the command-line arguments passed to the current process.
+///
+/// The first element is usually the name of the program.
+#[inline]
+pub fn args() -> &'static [&'static CharStar] {
+    ARGS.get()
+}
+
+/// Returns the environment of the current process, as it was when the program started.
+#[inline]
+pub fn vars() -> Env<'static> {
+    Env::new(VARS.get())
+}
+
+/// Returns the name of the current program (the first element of `argv`).
+///
+/// If it is not available, the empty string is returned.
+#[inline]
+pub fn program_name() -> &'static CharStar {
+    args().first().copied().unwrap_or(CharStar::EMPTY)
+}
+
+/// Captures the arguments of the program before `main` is called.
+///
+/// glibc passes `argc`, `argv` and `envp` to the functions of the `.init_array` section.
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+extern "C" fn capture_globals(argc: c_int, argv: *const *const c_char, envp: *const *const c_char) {
+    unsafe {
+        if !argv.is_null() && argc >= 0 {
+            ARGS.set(core::slice::from_raw_parts(
+                argv as *const &CharStar,
+                argc as usize,
+            ));
+        }
+
+        if !envp.is_null() {
+            let len = crate::__private::entry_point::null_terminated_array_len(envp);
+            VARS.set(core::slice::from_raw_parts(envp as *const &CharStar, len));
+        }
+    }
+}
+
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+#[used]
+#[link_section = ".init_array"]
+static CAPTURE_GLOBALS: extern "C" fn(c_int, *const *const c_char, *const *const c_char) =
+    capture_globals;
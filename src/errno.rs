@@ -3,6 +3,8 @@
 use core::ffi::c_int;
 use core::fmt;
 use core::mem::MaybeUninit;
+use core::sync::atomic::AtomicBool;
+use core::sync::atomic::Ordering::Relaxed;
 
 use crate::CharStar;
 
@@ -77,6 +79,9 @@ impl Errno {
     }
 
     /// Writes a description of this error to the provided buffer.
+    ///
+    /// If the description does not fit in the buffer, [`None`] is returned.
+    #[cfg(not(feature = "restrict-functions"))]
     pub fn write_description(self, buf: &mut [MaybeUninit<u8>]) -> Option<&CharStar> {
         let ret = unsafe { libc::strerror_r(self.0, buf.as_mut_ptr().cast(), buf.len()) };
 
@@ -86,69 +91,409 @@ impl Errno {
             None
         }
     }
+
+    /// Writes a description of this error to the provided buffer.
+    ///
+    /// If the description does not fit in the buffer, [`None`] is returned.
+    ///
+    /// This function is used when the `restrict-functions` feature is enabled. The description
+    /// comes from the built-in message table (see [`description`](Self::description)).
+    #[cfg(feature = "restrict-functions")]
+    pub fn write_description(self, buf: &mut [MaybeUninit<u8>]) -> Option<&CharStar> {
+        let desc = self.description()?;
+
+        if desc.len() >= buf.len() {
+            return None;
+        }
+
+        unsafe {
+            let p = buf.as_mut_ptr().cast::<u8>();
+            p.copy_from_nonoverlapping(desc.as_ptr(), desc.len());
+            p.add(desc.len()).write(0);
+            Some(CharStar::from_ptr(p.cast()))
+        }
+    }
+}
+
+/// Expands to the symbolic name of an [`Errno`] constant.
+macro_rules! errno_name {
+    ($name:ident) => {
+        concat!("E", stringify!($name))
+    };
+    ($name:ident, $sym:literal) => {
+        $sym
+    };
 }
 
 /// A helper to help define constants for [`Errno`].
+///
+/// Each constant is associated with a message, which is used as its documentation and by the
+/// [`Display`](fmt::Display) implementation of [`Errno`] when `strerror_r` cannot be used.
+///
+/// Each constant may be restricted to some platforms with a single `#[cfg(...)]` attribute,
+/// placed after its additional documentation. The symbolic name of the constant is its
+/// identifier prefixed with `E`, unless it is explicitly provided with `as "NAME"`.
 macro_rules! define_Errno_constants {
     ($(
-        $(#[$($attrs:meta)*])*
-        pub const $name:ident = $value:expr;
+        $(#[doc = $doc:literal])*
+        $(#[cfg($cfg:meta)])?
+        pub const $name:ident $(as $sym:literal)? = $value:expr => $message:literal;
     )*) => {
         impl Errno {
             $(
-                $(#[$($attrs)*])*
+                #[doc = concat!($message, ".")]
+                $(#[doc = ""] #[doc = $doc])*
+                $(#[cfg($cfg)])?
                 pub const $name: Self = Self($value);
             )*
-        }
 
-        impl fmt::Debug for Errno {
-            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-                write!(f, "\"{self}\" (")?;
+            /// Returns a description of this error from the built-in message table, or [`None`]
+            /// if the error code is not known.
+            ///
+            /// Unlike [`write_description`](Self::write_description), this function never calls
+            /// into the C library.
+            pub const fn description(self) -> Option<&'static str> {
+                match self {
+                    Self::SUCCESS => Some("Success"),
+                    $(
+                        $(#[cfg($cfg)])?
+                        Self::$name => Some($message),
+                    )*
+                    _ => None,
+                }
+            }
 
-                match *self {
+            /// Returns the symbolic name of this error (e.g. `"ENOENT"`), or [`None`] if the
+            /// error code is not known.
+            const fn known_name(self) -> Option<&'static str> {
+                match self {
+                    Self::SUCCESS => Some("SUCCESS"),
                     $(
-                        Self::$name => f.write_str(stringify!($name))?,
+                        $(#[cfg($cfg)])?
+                        Self::$name => Some(errno_name!($name $(, $sym)?)),
                     )*
-                    _ => f.debug_tuple("Errno").field(&self.0).finish()?,
+                    _ => None,
                 }
+            }
 
-                write!(f, ")")
+            /// Returns the [`Errno`] whose symbolic name is `name` (e.g. `"ENOENT"`).
+            ///
+            /// Aliases such as `"EWOULDBLOCK"` are not recognized, only the canonical name
+            /// returned by [`name`](Self::name) is. `"SUCCESS"` maps to [`Errno::SUCCESS`].
+            pub fn from_name(name: &str) -> Option<Self> {
+                match name {
+                    "SUCCESS" => Some(Self::SUCCESS),
+                    $(
+                        $(#[cfg($cfg)])?
+                        errno_name!($name $(, $sym)?) => Some(Self::$name),
+                    )*
+                    _ => None,
+                }
             }
         }
     };
 }
 
+impl Errno {
+    /// Indicates that no error occured.
+    pub const SUCCESS: Self = Self(0);
+    /// Operation would block.
+    ///
+    /// This is the same value as [`Errno::AGAIN`].
+    pub const WOULDBLOCK: Self = Self(libc::EWOULDBLOCK);
+    /// Resource deadlock avoided.
+    ///
+    /// This is the same value as [`Errno::DEADLK`].
+    #[cfg(target_os = "linux")]
+    pub const DEADLOCK: Self = Self(libc::EDEADLOCK);
+    /// Operation not supported.
+    ///
+    /// On Linux, this is the same value as [`Errno::OPNOTSUPP`].
+    #[cfg(target_os = "linux")]
+    pub const NOTSUP: Self = Self(libc::ENOTSUP);
+
+    /// Returns the symbolic name of this error, such as `"ENOENT"`.
+    ///
+    /// [`Errno::SUCCESS`] is named `"SUCCESS"`, and error codes that are not known are named
+    /// `"UNKNOWN"`.
+    #[inline]
+    pub const fn name(self) -> &'static str {
+        match self.known_name() {
+            Some(name) => name,
+            None => "UNKNOWN",
+        }
+    }
+}
+
+impl fmt::Debug for Errno {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "\"{self}\" (")?;
+
+        match self.known_name() {
+            Some(name) => f.write_str(name)?,
+            None => f.debug_tuple("Errno").field(&self.0).finish()?,
+        }
+
+        write!(f, ")")
+    }
+}
+
 impl fmt::Display for Errno {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let mut buf: [MaybeUninit<u8>; 32] = uninit_array();
+        #[cfg(not(feature = "restrict-functions"))]
+        {
+            let mut buf: [MaybeUninit<u8>; 64] = uninit_array();
+
+            if let Some(desc) = self.write_description(&mut buf).and_then(|d| d.as_str()) {
+                return f.pad(desc);
+            }
+        }
+
+        match self.description() {
+            Some(desc) => f.pad(desc),
+            None => write!(f, "Unknown error {}", self.0),
+        }
+    }
+}
+
+/// Whether the blocking wrappers of this crate restart when they are interrupted by a signal.
+static RESTART_ON_INTERRUPT: AtomicBool = AtomicBool::new(true);
 
-        let desc = self
-            .write_description(&mut buf)
-            .and_then(|d| d.as_str())
-            .unwrap_or("Unknown error");
+/// Sets whether the blocking wrappers of this crate should transparently restart when they are
+/// interrupted by a signal.
+///
+/// This is enabled by default. Programs that install signal handlers and want to observe
+/// [`Errno::INTR`] (for example, to stop waiting when `SIGINT` is received) can disable it.
+///
+/// This policy applies to the following functions, and to every function built on them:
+///
+/// - Those that read or write a file descriptor: [`Fd::read`](crate::Fd::read),
+///   [`Fd::write`](crate::Fd::write), their vectored and positional forms, and the methods of
+///   [`EventFd`](crate::fd::EventFd), [`TimerFd`](crate::fd::TimerFd) and
+///   [`SignalFd`](crate::fd::SignalFd) that wait for a value.
+/// - Those that wait for another process for an unbounded amount of time:
+///   [`Fd::accept`](crate::Fd::accept), [`Fd::lock`](crate::Fd::lock),
+///   [`Pid::wait`](crate::Pid::wait) and [`Pid::wait_any`](crate::Pid::wait_any), as well as
+///   [`poll`](crate::fd::poll) and [`Poller::wait`](crate::fd::Poller::wait) when they are not
+///   given a timeout.
+/// - The provided methods of [`io::Read`](crate::io::Read) and [`io::Write`](crate::io::Write),
+///   and the methods of [`ReadBuffer`](crate::collections::ReadBuffer) that read, whatever the
+///   reader or writer.
+///
+/// Waits with a timeout report interruptions, since restarting them would make them last longer
+/// than requested.
+///
+/// # Returns
+///
+/// The previous value of the policy.
+#[inline]
+pub fn set_restart_on_interrupt(restart: bool) -> bool {
+    RESTART_ON_INTERRUPT.swap(restart, Relaxed)
+}
 
-        f.pad(desc)
+/// Returns whether the blocking wrappers of this crate transparently restart when they are
+/// interrupted by a signal.
+///
+/// See [`set_restart_on_interrupt`].
+#[inline]
+pub fn restart_on_interrupt() -> bool {
+    RESTART_ON_INTERRUPT.load(Relaxed)
+}
+
+/// Calls `f` until it does not fail with [`Errno::INTR`], unless the process disabled that
+/// behavior with [`set_restart_on_interrupt`].
+pub(crate) fn retry_on_interrupt<T>(mut f: impl FnMut() -> Result<T>) -> Result<T> {
+    loop {
+        match f() {
+            Err(Errno::INTR) if restart_on_interrupt() => continue,
+            res => break res,
+        }
     }
 }
 
 /// A helper to create an array of [`MaybeUninit`] values.
+#[cfg(not(feature = "restrict-functions"))]
 fn uninit_array<T, const N: usize>() -> [MaybeUninit<T>; N] {
     unsafe { MaybeUninit::uninit().assume_init() }
 }
 
 define_Errno_constants! {
-    /// Indicates that no error occured.
-    pub const SUCCESS = 0;
-    /// Indicates that an invalid argument was provided.
-    pub const INVAL = libc::EINVAL;
-    /// Indicates that the provided buffer was too small.
-    pub const WOULDBLOCK = libc::EWOULDBLOCK;
-    /// Indicates that the operation was interrupted by a signal.
-    pub const INTR = libc::EINTR;
-    /// The system is out of memory.
-    pub const NOMEM = libc::ENOMEM;
-    /// The connection was reset by the peer.
-    pub const CONNRESET = libc::ECONNRESET;
-    /// The connection was aborted by the peer.
-    pub const NOEXEC = libc::ENOEXEC;
+    pub const PERM = libc::EPERM => "Operation not permitted";
+    pub const NOENT = libc::ENOENT => "No such file or directory";
+    pub const SRCH = libc::ESRCH => "No such process";
+    pub const INTR = libc::EINTR => "Interrupted system call";
+    pub const IO = libc::EIO => "Input/output error";
+    pub const NXIO = libc::ENXIO => "No such device or address";
+    /// The symbolic name of this error is `E2BIG`.
+    pub const TOOBIG as "E2BIG" = libc::E2BIG => "Argument list too long";
+    pub const NOEXEC = libc::ENOEXEC => "Exec format error";
+    pub const BADF = libc::EBADF => "Bad file descriptor";
+    pub const CHILD = libc::ECHILD => "No child processes";
+    pub const AGAIN = libc::EAGAIN => "Resource temporarily unavailable";
+    pub const NOMEM = libc::ENOMEM => "Cannot allocate memory";
+    pub const ACCES = libc::EACCES => "Permission denied";
+    pub const FAULT = libc::EFAULT => "Bad address";
+    pub const NOTBLK = libc::ENOTBLK => "Block device required";
+    pub const BUSY = libc::EBUSY => "Device or resource busy";
+    pub const EXIST = libc::EEXIST => "File exists";
+    pub const XDEV = libc::EXDEV => "Invalid cross-device link";
+    pub const NODEV = libc::ENODEV => "No such device";
+    pub const NOTDIR = libc::ENOTDIR => "Not a directory";
+    pub const ISDIR = libc::EISDIR => "Is a directory";
+    pub const INVAL = libc::EINVAL => "Invalid argument";
+    pub const NFILE = libc::ENFILE => "Too many open files in system";
+    pub const MFILE = libc::EMFILE => "Too many open files";
+    pub const NOTTY = libc::ENOTTY => "Inappropriate ioctl for device";
+    pub const TXTBSY = libc::ETXTBSY => "Text file busy";
+    pub const FBIG = libc::EFBIG => "File too large";
+    pub const NOSPC = libc::ENOSPC => "No space left on device";
+    pub const SPIPE = libc::ESPIPE => "Illegal seek";
+    pub const ROFS = libc::EROFS => "Read-only file system";
+    pub const MLINK = libc::EMLINK => "Too many links";
+    pub const PIPE = libc::EPIPE => "Broken pipe";
+    pub const DOM = libc::EDOM => "Numerical argument out of domain";
+    pub const RANGE = libc::ERANGE => "Numerical result out of range";
+    pub const DEADLK = libc::EDEADLK => "Resource deadlock avoided";
+    pub const NAMETOOLONG = libc::ENAMETOOLONG => "File name too long";
+    pub const NOLCK = libc::ENOLCK => "No locks available";
+    pub const NOSYS = libc::ENOSYS => "Function not implemented";
+    pub const NOTEMPTY = libc::ENOTEMPTY => "Directory not empty";
+    pub const LOOP = libc::ELOOP => "Too many levels of symbolic links";
+    pub const NOMSG = libc::ENOMSG => "No message of desired type";
+    pub const IDRM = libc::EIDRM => "Identifier removed";
+    #[cfg(target_os = "linux")]
+    pub const CHRNG = libc::ECHRNG => "Channel number out of range";
+    #[cfg(target_os = "linux")]
+    pub const L2NSYNC = libc::EL2NSYNC => "Level 2 not synchronized";
+    #[cfg(target_os = "linux")]
+    pub const L3HLT = libc::EL3HLT => "Level 3 halted";
+    #[cfg(target_os = "linux")]
+    pub const L3RST = libc::EL3RST => "Level 3 reset";
+    #[cfg(target_os = "linux")]
+    pub const LNRNG = libc::ELNRNG => "Link number out of range";
+    #[cfg(target_os = "linux")]
+    pub const UNATCH = libc::EUNATCH => "Protocol driver not attached";
+    #[cfg(target_os = "linux")]
+    pub const NOCSI = libc::ENOCSI => "No CSI structure available";
+    #[cfg(target_os = "linux")]
+    pub const L2HLT = libc::EL2HLT => "Level 2 halted";
+    #[cfg(target_os = "linux")]
+    pub const BADE = libc::EBADE => "Invalid exchange";
+    #[cfg(target_os = "linux")]
+    pub const BADR = libc::EBADR => "Invalid request descriptor";
+    #[cfg(target_os = "linux")]
+    pub const XFULL = libc::EXFULL => "Exchange full";
+    #[cfg(target_os = "linux")]
+    pub const NOANO = libc::ENOANO => "No anode";
+    #[cfg(target_os = "linux")]
+    pub const BADRQC = libc::EBADRQC => "Invalid request code";
+    #[cfg(target_os = "linux")]
+    pub const BADSLT = libc::EBADSLT => "Invalid slot";
+    #[cfg(target_os = "linux")]
+    pub const BFONT = libc::EBFONT => "Bad font file format";
+    pub const NOSTR = libc::ENOSTR => "Device not a stream";
+    pub const NODATA = libc::ENODATA => "No data available";
+    pub const TIME = libc::ETIME => "Timer expired";
+    pub const NOSR = libc::ENOSR => "Out of streams resources";
+    #[cfg(target_os = "linux")]
+    pub const NONET = libc::ENONET => "Machine is not on the network";
+    #[cfg(target_os = "linux")]
+    pub const NOPKG = libc::ENOPKG => "Package not installed";
+    pub const REMOTE = libc::EREMOTE => "Object is remote";
+    pub const NOLINK = libc::ENOLINK => "Link has been severed";
+    #[cfg(target_os = "linux")]
+    pub const ADV = libc::EADV => "Advertise error";
+    #[cfg(target_os = "linux")]
+    pub const SRMNT = libc::ESRMNT => "Srmount error";
+    #[cfg(target_os = "linux")]
+    pub const COMM = libc::ECOMM => "Communication error on send";
+    pub const PROTO = libc::EPROTO => "Protocol error";
+    pub const MULTIHOP = libc::EMULTIHOP => "Multihop attempted";
+    #[cfg(target_os = "linux")]
+    pub const DOTDOT = libc::EDOTDOT => "RFS specific error";
+    pub const BADMSG = libc::EBADMSG => "Bad message";
+    pub const OVERFLOW = libc::EOVERFLOW => "Value too large for defined data type";
+    #[cfg(target_os = "linux")]
+    pub const NOTUNIQ = libc::ENOTUNIQ => "Name not unique on network";
+    #[cfg(target_os = "linux")]
+    pub const BADFD = libc::EBADFD => "File descriptor in bad state";
+    #[cfg(target_os = "linux")]
+    pub const REMCHG = libc::EREMCHG => "Remote address changed";
+    #[cfg(target_os = "linux")]
+    pub const LIBACC = libc::ELIBACC => "Can not access a needed shared library";
+    #[cfg(target_os = "linux")]
+    pub const LIBBAD = libc::ELIBBAD => "Accessing a corrupted shared library";
+    #[cfg(target_os = "linux")]
+    pub const LIBSCN = libc::ELIBSCN => ".lib section in a.out corrupted";
+    #[cfg(target_os = "linux")]
+    pub const LIBMAX = libc::ELIBMAX => "Attempting to link in too many shared libraries";
+    #[cfg(target_os = "linux")]
+    pub const LIBEXEC = libc::ELIBEXEC => "Cannot exec a shared library directly";
+    pub const ILSEQ = libc::EILSEQ => "Invalid or incomplete multibyte or wide character";
+    #[cfg(target_os = "linux")]
+    pub const RESTART = libc::ERESTART => "Interrupted system call should be restarted";
+    #[cfg(target_os = "linux")]
+    pub const STRPIPE = libc::ESTRPIPE => "Streams pipe error";
+    pub const USERS = libc::EUSERS => "Too many users";
+    pub const NOTSOCK = libc::ENOTSOCK => "Socket operation on non-socket";
+    pub const DESTADDRREQ = libc::EDESTADDRREQ => "Destination address required";
+    pub const MSGSIZE = libc::EMSGSIZE => "Message too long";
+    pub const PROTOTYPE = libc::EPROTOTYPE => "Protocol wrong type for socket";
+    pub const NOPROTOOPT = libc::ENOPROTOOPT => "Protocol not available";
+    pub const PROTONOSUPPORT = libc::EPROTONOSUPPORT => "Protocol not supported";
+    pub const SOCKTNOSUPPORT = libc::ESOCKTNOSUPPORT => "Socket type not supported";
+    pub const OPNOTSUPP = libc::EOPNOTSUPP => "Operation not supported";
+    #[cfg(not(target_os = "linux"))]
+    pub const NOTSUP = libc::ENOTSUP => "Operation not supported";
+    pub const PFNOSUPPORT = libc::EPFNOSUPPORT => "Protocol family not supported";
+    pub const AFNOSUPPORT = libc::EAFNOSUPPORT => "Address family not supported by protocol";
+    pub const ADDRINUSE = libc::EADDRINUSE => "Address already in use";
+    pub const ADDRNOTAVAIL = libc::EADDRNOTAVAIL => "Cannot assign requested address";
+    pub const NETDOWN = libc::ENETDOWN => "Network is down";
+    pub const NETUNREACH = libc::ENETUNREACH => "Network is unreachable";
+    pub const NETRESET = libc::ENETRESET => "Network dropped connection on reset";
+    pub const CONNABORTED = libc::ECONNABORTED => "Software caused connection abort";
+    pub const CONNRESET = libc::ECONNRESET => "Connection reset by peer";
+    pub const NOBUFS = libc::ENOBUFS => "No buffer space available";
+    pub const ISCONN = libc::EISCONN => "Transport endpoint is already connected";
+    pub const NOTCONN = libc::ENOTCONN => "Transport endpoint is not connected";
+    pub const SHUTDOWN = libc::ESHUTDOWN => "Cannot send after transport endpoint shutdown";
+    pub const TOOMANYREFS = libc::ETOOMANYREFS => "Too many references: cannot splice";
+    pub const TIMEDOUT = libc::ETIMEDOUT => "Connection timed out";
+    pub const CONNREFUSED = libc::ECONNREFUSED => "Connection refused";
+    pub const HOSTDOWN = libc::EHOSTDOWN => "Host is down";
+    pub const HOSTUNREACH = libc::EHOSTUNREACH => "No route to host";
+    pub const ALREADY = libc::EALREADY => "Operation already in progress";
+    pub const INPROGRESS = libc::EINPROGRESS => "Operation now in progress";
+    pub const STALE = libc::ESTALE => "Stale file handle";
+    #[cfg(target_os = "linux")]
+    pub const UCLEAN = libc::EUCLEAN => "Structure needs cleaning";
+    #[cfg(target_os = "linux")]
+    pub const NOTNAM = libc::ENOTNAM => "Not a XENIX named type file";
+    #[cfg(target_os = "linux")]
+    pub const NAVAIL = libc::ENAVAIL => "No XENIX semaphores available";
+    #[cfg(target_os = "linux")]
+    pub const ISNAM = libc::EISNAM => "Is a named type file";
+    #[cfg(target_os = "linux")]
+    pub const REMOTEIO = libc::EREMOTEIO => "Remote I/O error";
+    pub const DQUOT = libc::EDQUOT => "Disk quota exceeded";
+    #[cfg(target_os = "linux")]
+    pub const NOMEDIUM = libc::ENOMEDIUM => "No medium found";
+    #[cfg(target_os = "linux")]
+    pub const MEDIUMTYPE = libc::EMEDIUMTYPE => "Wrong medium type";
+    pub const CANCELED = libc::ECANCELED => "Operation canceled";
+    #[cfg(target_os = "linux")]
+    pub const NOKEY = libc::ENOKEY => "Required key not available";
+    #[cfg(target_os = "linux")]
+    pub const KEYEXPIRED = libc::EKEYEXPIRED => "Key has expired";
+    #[cfg(target_os = "linux")]
+    pub const KEYREVOKED = libc::EKEYREVOKED => "Key has been revoked";
+    #[cfg(target_os = "linux")]
+    pub const KEYREJECTED = libc::EKEYREJECTED => "Key was rejected by service";
+    pub const OWNERDEAD = libc::EOWNERDEAD => "Owner died";
+    pub const NOTRECOVERABLE = libc::ENOTRECOVERABLE => "State not recoverable";
+    #[cfg(target_os = "linux")]
+    pub const RFKILL = libc::ERFKILL => "Operation not possible due to RF-kill";
+    #[cfg(target_os = "linux")]
+    pub const HWPOISON = libc::EHWPOISON => "Memory page has hardware error";
 }
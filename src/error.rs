@@ -0,0 +1,194 @@
+//! Defines the [`Error`] type, which adds context to an [`Errno`].
+
+use core::fmt;
+
+#[cfg(feature = "alloc")]
+use crate::charstar::CharStarBox;
+use crate::{CharStar, Errno};
+
+/// The path associated with an [`Error`].
+#[derive(Clone)]
+enum ErrorPath<'a> {
+    /// The path is borrowed from the caller.
+    Borrowed(&'a CharStar),
+    /// The path is owned by the error.
+    #[cfg(feature = "alloc")]
+    Owned(CharStarBox),
+}
+
+impl ErrorPath<'_> {
+    /// Returns the path.
+    #[inline]
+    fn as_charstar(&self) -> &CharStar {
+        match self {
+            Self::Borrowed(path) => path,
+            #[cfg(feature = "alloc")]
+            Self::Owned(path) => path,
+        }
+    }
+}
+
+/// An [`Errno`] along with the operation that produced it and, optionally, the path it was
+/// operating on.
+///
+/// The path may either be borrowed, or owned by the error when it must outlive the original
+/// string (see [`into_owned`](Self::into_owned), which requires the `alloc` feature).
+///
+/// # Examples
+///
+/// ```ignore
+/// use ft::{charstar, Fd, ResultExt};
+/// use ft::fd::OpenFlags;
+///
+/// let path = charstar!("foo.conf");
+/// let err = Fd::open(path, OpenFlags::READ_ONLY).path_context("open", path).unwrap_err();
+/// assert_eq!(err.to_string(), "open \"foo.conf\": No such file or directory");
+/// ```
+#[derive(Clone)]
+pub struct Error<'a> {
+    /// The underlying error code.
+    errno: Errno,
+    /// The name of the operation that failed.
+    ///
+    /// When empty, the error has no context.
+    operation: &'static str,
+    /// The path the operation was working on.
+    path: Option<ErrorPath<'a>>,
+}
+
+impl<'a> Error<'a> {
+    /// Creates a new [`Error`] with the provided operation name.
+    #[inline]
+    pub const fn new(errno: Errno, operation: &'static str) -> Self {
+        Self {
+            errno,
+            operation,
+            path: None,
+        }
+    }
+
+    /// Associates a borrowed path with this error.
+    #[inline]
+    pub fn with_path(self, path: &'a CharStar) -> Self {
+        Self {
+            path: Some(ErrorPath::Borrowed(path)),
+            ..self
+        }
+    }
+
+    /// Associates an owned path with this error.
+    #[inline]
+    #[cfg(feature = "alloc")]
+    pub fn with_boxed_path(self, path: CharStarBox) -> Self {
+        Self {
+            path: Some(ErrorPath::Owned(path)),
+            ..self
+        }
+    }
+
+    /// Returns the underlying error code.
+    #[inline]
+    pub const fn errno(&self) -> Errno {
+        self.errno
+    }
+
+    /// Returns the name of the operation that failed.
+    ///
+    /// This is the empty string if the error has no context.
+    #[inline]
+    pub const fn operation(&self) -> &'static str {
+        self.operation
+    }
+
+    /// Returns the path associated with this error, if any.
+    #[inline]
+    pub fn path(&self) -> Option<&CharStar> {
+        self.path.as_ref().map(ErrorPath::as_charstar)
+    }
+
+    /// Makes sure that this [`Error`] does not borrow anything, copying the path to the heap if
+    /// needed.
+    ///
+    /// If the path cannot be copied because the system is out of memory, it is discarded.
+    #[cfg(feature = "alloc")]
+    pub fn into_owned(self) -> Error<'static> {
+        let path = match self.path {
+            Some(ErrorPath::Borrowed(path)) => CharStarBox::from_bytes(path.as_bytes())
+                .ok()
+                .map(ErrorPath::Owned),
+            Some(ErrorPath::Owned(path)) => Some(ErrorPath::Owned(path)),
+            None => None,
+        };
+
+        Error {
+            errno: self.errno,
+            operation: self.operation,
+            path,
+        }
+    }
+}
+
+impl From<Errno> for Error<'_> {
+    #[inline]
+    fn from(errno: Errno) -> Self {
+        Self::new(errno, "")
+    }
+}
+
+impl From<Error<'_>> for Errno {
+    #[inline]
+    fn from(err: Error) -> Self {
+        err.errno
+    }
+}
+
+impl fmt::Display for Error<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.operation.is_empty() {
+            f.write_str(self.operation)?;
+
+            if let Some(path) = self.path() {
+                write!(f, " {path:?}")?;
+            }
+
+            f.write_str(": ")?;
+        } else if let Some(path) = self.path() {
+            write!(f, "{path:?}: ")?;
+        }
+
+        fmt::Display::fmt(&self.errno, f)
+    }
+}
+
+impl fmt::Debug for Error<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Error")
+            .field("errno", &self.errno)
+            .field("operation", &self.operation)
+            .field("path", &self.path())
+            .finish()
+    }
+}
+
+/// An extension trait for [`Result`](crate::Result) that adds context to its error.
+pub trait ResultExt<T> {
+    /// Attaches the name of the failing operation to the error.
+    fn context(self, operation: &'static str) -> Result<T, Error<'static>>;
+
+    /// Attaches the name of the failing operation and the path it was working on to the error.
+    ///
+    /// The path is borrowed. Use [`Error::into_owned`] if the error must outlive it.
+    fn path_context<'a>(self, operation: &'static str, path: &'a CharStar) -> Result<T, Error<'a>>;
+}
+
+impl<T> ResultExt<T> for crate::Result<T> {
+    #[inline]
+    fn context(self, operation: &'static str) -> Result<T, Error<'static>> {
+        self.map_err(|errno| Error::new(errno, operation))
+    }
+
+    #[inline]
+    fn path_context<'a>(self, operation: &'static str, path: &'a CharStar) -> Result<T, Error<'a>> {
+        self.map_err(|errno| Error::new(errno, operation).with_path(path))
+    }
+}
@@ -0,0 +1,139 @@
+//! Duplication of file descriptors.
+
+use crate::{Errno, Fd, Result};
+
+impl Fd {
+    /// Duplicates this file descriptor, using the lowest-numbered file descriptor available.
+    ///
+    /// The new file descriptor refers to the same open file description as this one, meaning
+    /// that they share the same file offset and status flags.
+    ///
+    /// # Returns
+    ///
+    /// The new file descriptor.
+    #[inline]
+    #[doc(alias = "dup")]
+    pub fn duplicate(self) -> Result<Fd> {
+        let ret = unsafe { libc::dup(self.to_raw()) };
+        if ret < 0 {
+            Err(Errno::last())
+        } else {
+            Ok(Fd::from_raw(ret))
+        }
+    }
+
+    /// Like [`duplicate`](Self::duplicate), but the new file descriptor is automatically closed
+    /// when the process calls `exec`.
+    ///
+    /// Unlike calling [`set_cloexec`](Self::set_cloexec) after the fact, this leaves no window
+    /// during which another thread could `exec` with the new file descriptor open.
+    #[inline]
+    #[doc(alias = "F_DUPFD_CLOEXEC")]
+    pub fn duplicate_cloexec(self) -> Result<Fd> {
+        let ret = unsafe { libc::fcntl(self.to_raw(), libc::F_DUPFD_CLOEXEC, 0) };
+        if ret < 0 {
+            Err(Errno::last())
+        } else {
+            Ok(Fd::from_raw(ret))
+        }
+    }
+
+    /// Duplicates this file descriptor into `target`, closing `target` first if it was open.
+    ///
+    /// When `cloexec` is set, the new file descriptor is automatically closed when the process
+    /// calls `exec`.
+    ///
+    /// # Returns
+    ///
+    /// The new file descriptor (which is `target`).
+    ///
+    /// # Remarks
+    ///
+    /// If `self` and `target` are the same file descriptor, this function only sets its
+    /// close-on-exec flag when `cloexec` is set.
+    #[doc(alias = "dup2")]
+    #[doc(alias = "dup3")]
+    pub fn duplicate_to(self, target: Fd, cloexec: bool) -> Result<Fd> {
+        if self == target {
+            if cloexec {
+                self.set_cloexec(true)?;
+            }
+            return Ok(target);
+        }
+
+        #[cfg(target_os = "linux")]
+        let ret = unsafe {
+            let flags = if cloexec { libc::O_CLOEXEC } else { 0 };
+            libc::dup3(self.to_raw(), target.to_raw(), flags)
+        };
+
+        #[cfg(not(target_os = "linux"))]
+        let ret = unsafe {
+            let ret = libc::dup2(self.to_raw(), target.to_raw());
+            if ret >= 0 && cloexec {
+                libc::fcntl(ret, libc::F_SETFD, libc::FD_CLOEXEC);
+            }
+            ret
+        };
+
+        if ret < 0 {
+            Err(Errno::last())
+        } else {
+            Ok(Fd::from_raw(ret))
+        }
+    }
+
+    /// Temporarily makes this file descriptor refer to the same file as `source`.
+    ///
+    /// This is typically used to redirect one of the standard streams, such as [`Fd::STDOUT`],
+    /// to a file or a pipe.
+    ///
+    /// # Returns
+    ///
+    /// A guard that restores the original file, and whether it was closed on `exec`, when
+    /// dropped.
+    #[doc(alias = "dup2")]
+    pub fn redirect(self, source: Fd) -> Result<RedirectGuard> {
+        let cloexec = self.is_cloexec()?;
+        let saved = self.duplicate_cloexec()?;
+
+        if let Err(err) = source.duplicate_to(self, false) {
+            let _ = saved.close();
+            return Err(err);
+        }
+
+        Ok(RedirectGuard {
+            target: self,
+            saved,
+            cloexec,
+        })
+    }
+}
+
+/// A guard that restores a redirected file descriptor when dropped.
+///
+/// This is returned by [`Fd::redirect`].
+#[derive(Debug)]
+pub struct RedirectGuard {
+    /// The file descriptor that was redirected.
+    target: Fd,
+    /// A copy of the original file descriptor.
+    saved: Fd,
+    /// Whether the original file descriptor was closed on `exec`.
+    cloexec: bool,
+}
+
+impl RedirectGuard {
+    /// Returns the file descriptor that was redirected.
+    #[inline]
+    pub fn target(&self) -> Fd {
+        self.target
+    }
+}
+
+impl Drop for RedirectGuard {
+    fn drop(&mut self) {
+        let _ = self.saved.duplicate_to(self.target, self.cloexec);
+        let _ = self.saved.close();
+    }
+}
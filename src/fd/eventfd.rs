@@ -0,0 +1,99 @@
+//! Event file descriptors.
+
+use core::ffi::c_int;
+use core::ops::Deref;
+
+use bitflags::bitflags;
+
+#[cfg(feature = "futures")]
+use crate::futures;
+use crate::{Errno, Fd, File, Result};
+
+bitflags! {
+    /// Flags that can be passed to [`EventFd::new`].
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub struct EventFdFlags: c_int {
+        /// The file descriptor is closed automatically when the process calls `exec`.
+        const CLOEXEC = libc::EFD_CLOEXEC;
+        /// The file descriptor is opened in non-blocking mode.
+        ///
+        /// This is required to use [`EventFd::async_consume`].
+        const NON_BLOCKING = libc::EFD_NONBLOCK;
+        /// [`EventFd::consume`] decrements the counter by one instead of resetting it.
+        const SEMAPHORE = libc::EFD_SEMAPHORE;
+    }
+}
+
+/// A file descriptor that holds a counter, used to wake up a poll loop from another thread or
+/// from a signal handler.
+///
+/// The file descriptor is readable whenever the counter is not zero, meaning that it can be
+/// registered with [`poll`](crate::fd::poll) or a [`Poller`](crate::fd::Poller) like any other
+/// file descriptor. It is closed when the [`EventFd`] is dropped.
+#[derive(Debug)]
+#[doc(alias = "eventfd")]
+pub struct EventFd(File);
+
+impl EventFd {
+    /// Creates a new [`EventFd`] whose counter starts at `initial`.
+    pub fn new(initial: u32, flags: EventFdFlags) -> Result<Self> {
+        let ret = unsafe { libc::eventfd(initial, flags.bits()) };
+        if ret < 0 {
+            Err(Errno::last())
+        } else {
+            Ok(Self(File::from_raw(ret)))
+        }
+    }
+
+    /// Adds `value` to the counter, waking up the readers of the file descriptor.
+    ///
+    /// # Errors
+    ///
+    /// If the counter would overflow, this function blocks, or fails with
+    /// [`Errno::WOULDBLOCK`] in non-blocking mode.
+    pub fn notify(&self, value: u64) -> Result<()> {
+        let count = self.0.write(&value.to_ne_bytes())?;
+        debug_assert_eq!(count, 8);
+        Ok(())
+    }
+
+    /// Waits for the counter to be non-zero, and resets it.
+    ///
+    /// # Returns
+    ///
+    /// The value of the counter, or `1` in [`SEMAPHORE`](EventFdFlags::SEMAPHORE) mode, where
+    /// the counter is decremented instead.
+    #[inline]
+    pub fn consume(&self) -> Result<u64> {
+        unsafe { self.0.read_value() }
+    }
+
+    /// Attempts to consume the counter.
+    ///
+    /// If the counter is zero, this function returns `Pending` and schedules the current task
+    /// to be woken up when it is not anymore.
+    #[inline]
+    #[cfg(feature = "rt-single-thread")]
+    pub fn poll_consume(&self, cx: &mut core::task::Context) -> core::task::Poll<Result<u64>> {
+        unsafe { self.0.poll_read_value(cx) }
+    }
+
+    /// Like [`consume`](Self::consume), but asynchronous.
+    ///
+    /// The file descriptor must have been created with
+    /// [`NON_BLOCKING`](EventFdFlags::NON_BLOCKING).
+    #[inline]
+    #[cfg(feature = "futures")]
+    pub fn async_consume(&self) -> futures::Consume<'_> {
+        futures::Consume { event: self }
+    }
+}
+
+impl Deref for EventFd {
+    type Target = Fd;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
@@ -25,6 +25,27 @@ bitflags! {
         const APPEND = libc::O_APPEND;
         /// The file should be opened in non-blocking mode.
         const NON_BLOCKING = libc::O_NONBLOCK;
+        /// Opening the file should fail if it is not a directory.
+        const DIRECTORY = libc::O_DIRECTORY;
+        /// The file descriptor should be closed automatically when a new program is executed.
+        const CLOEXEC = libc::O_CLOEXEC;
+        /// Used with `CREATE`, opening the file should fail if it already exists.
+        ///
+        /// The check and the creation are performed atomically, which makes this flag suitable
+        /// for lock files.
+        const EXCL = libc::O_EXCL;
+        /// Opening the file should fail if the last component of the path is a symbolic link.
+        const NOFOLLOW = libc::O_NOFOLLOW;
+        /// Writes should only complete once the data and metadata have reached the underlying
+        /// hardware.
+        const SYNC = libc::O_SYNC;
+        /// Opening a terminal should not make it the controlling terminal of the process.
+        const NOCTTY = libc::O_NOCTTY;
+        /// An unnamed temporary file should be created in the directory referred to by the path.
+        ///
+        /// This flag must be used with `WRITE_ONLY` or `READ_WRITE`.
+        #[cfg(target_os = "linux")]
+        const TMPFILE = libc::O_TMPFILE;
     }
 }
 
@@ -50,6 +71,16 @@ bitflags! {
         const OTHER_WRITE = libc::S_IWOTH;
         /// The file can be executed by others.
         const OTHER_EXECUTE = libc::S_IXOTH;
+        /// The file is executed with the privileges of its owner.
+        #[doc(alias = "S_ISUID")]
+        const SET_UID = libc::S_ISUID;
+        /// The file is executed with the privileges of its group. On directories, new files
+        /// inherit the group of the directory.
+        #[doc(alias = "S_ISGID")]
+        const SET_GID = libc::S_ISGID;
+        /// On directories, files can only be removed or renamed by their owner.
+        #[doc(alias = "S_ISVTX")]
+        const STICKY = libc::S_ISVTX;
     }
 }
 
@@ -68,6 +99,11 @@ impl Mode {
 
 impl Fd {
     /// Sets the [`OpenFlags`] for this file descriptor.
+    ///
+    /// # Remarks
+    ///
+    /// All the status flags of the file descriptor are replaced. Use
+    /// [`set_nonblocking`](Self::set_nonblocking) to change a single flag.
     #[inline]
     #[doc(alias = "fcntl")]
     pub fn set_flags(self, flags: OpenFlags) -> Result<()> {
@@ -90,4 +126,54 @@ impl Fd {
             Ok(OpenFlags::from_bits_retain(ret))
         }
     }
+
+    /// Sets whether this file descriptor is in non-blocking mode, preserving its other status
+    /// flags.
+    #[doc(alias = "O_NONBLOCK")]
+    pub fn set_nonblocking(self, yes: bool) -> Result<()> {
+        let flags = self.get_flags()?;
+        if flags.contains(OpenFlags::NON_BLOCKING) == yes {
+            return Ok(());
+        }
+        self.set_flags(flags ^ OpenFlags::NON_BLOCKING)
+    }
+
+    /// Returns whether this file descriptor is automatically closed when the process calls
+    /// `exec`.
+    #[inline]
+    #[doc(alias = "FD_CLOEXEC")]
+    pub fn is_cloexec(self) -> Result<bool> {
+        let ret = unsafe { libc::fcntl(self.0, libc::F_GETFD) };
+        if ret < 0 {
+            Err(Errno::last())
+        } else {
+            Ok(ret & libc::FD_CLOEXEC != 0)
+        }
+    }
+
+    /// Sets whether this file descriptor is automatically closed when the process calls
+    /// `exec`.
+    #[doc(alias = "FD_CLOEXEC")]
+    pub fn set_cloexec(self, yes: bool) -> Result<()> {
+        let ret = unsafe { libc::fcntl(self.0, libc::F_GETFD) };
+        if ret < 0 {
+            return Err(Errno::last());
+        }
+
+        let flags = if yes {
+            ret | libc::FD_CLOEXEC
+        } else {
+            ret & !libc::FD_CLOEXEC
+        };
+        if flags == ret {
+            return Ok(());
+        }
+
+        let ret = unsafe { libc::fcntl(self.0, libc::F_SETFD, flags) };
+        if ret == 0 {
+            Ok(())
+        } else {
+            Err(Errno::last())
+        }
+    }
 }
@@ -5,6 +5,7 @@ use core::mem::MaybeUninit;
 use libc::c_uint;
 
 use super::{Mode, OpenFlags};
+use crate::errno::retry_on_interrupt;
 #[cfg(feature = "futures")]
 use crate::futures;
 use crate::{CharStar, Errno, Fd, File, Result};
@@ -43,6 +44,26 @@ impl Fd {
         }
     }
 
+    /// Opens a file relative to the directory referred to by `dir`.
+    ///
+    /// If `path` is absolute, `dir` is ignored. Passing [`Fd::CWD`] makes this function
+    /// equivalent to [`open_with_mode`](Self::open_with_mode).
+    ///
+    /// # Returns
+    ///
+    /// A [`Fd`] instance representing the opened file.
+    #[inline]
+    #[doc(alias = "openat")]
+    pub fn open_at(dir: Fd, path: &CharStar, flags: OpenFlags, mode: Mode) -> Result<Self> {
+        let res =
+            unsafe { libc::openat(dir.0, path.as_ptr(), flags.bits(), mode.bits() as c_uint) };
+        if res < 0 {
+            Err(Errno::last())
+        } else {
+            Ok(Self(res))
+        }
+    }
+
     /// Writes some amount of the provided buffer to the file descriptor.
     ///
     /// # Notes
@@ -56,12 +77,14 @@ impl Fd {
     /// The number of bytes written to the file descriptor.
     #[inline]
     pub fn write(self, data: &[u8]) -> Result<usize> {
-        let res = unsafe { libc::write(self.0, data.as_ptr() as *const c_void, data.len()) };
-        if res < 0 {
-            Err(Errno::last())
-        } else {
-            Ok(res as usize)
-        }
+        retry_on_interrupt(|| {
+            let res = unsafe { libc::write(self.0, data.as_ptr() as *const c_void, data.len()) };
+            if res < 0 {
+                Err(Errno::last())
+            } else {
+                Ok(res as usize)
+            }
+        })
     }
 
     /// Like [`write`](Self::write), but async.
@@ -76,10 +99,18 @@ impl Fd {
     ///
     /// This function simply calls [`write`](Fd::write) in a loop until the entire buffer
     /// has been written or an error occurs.
+    ///
+    /// # Errors
+    ///
+    /// If the file descriptor stops accepting data before everything has been written, this
+    /// function fails with [`Errno::NOSPC`].
     #[doc(alias = "write")]
     pub fn write_all(self, mut buf: &[u8]) -> Result<()> {
         while !buf.is_empty() {
             let n = self.write(buf)?;
+            if n == 0 {
+                return Err(Errno::NOSPC);
+            }
 
             // SAFETY:
             //  We know that `write` wrote `n` bytes, so it's safe to advance the buffer
@@ -161,12 +192,14 @@ impl Fd {
     /// even if more data is available for reading.
     #[inline]
     pub fn read(self, buf: &mut [MaybeUninit<u8>]) -> Result<usize> {
-        let res = unsafe { libc::read(self.0, buf.as_mut_ptr() as *mut c_void, buf.len()) };
-        if res < 0 {
-            Err(Errno::last())
-        } else {
-            Ok(res as usize)
-        }
+        retry_on_interrupt(|| {
+            let res = unsafe { libc::read(self.0, buf.as_mut_ptr() as *mut c_void, buf.len()) };
+            if res < 0 {
+                Err(Errno::last())
+            } else {
+                Ok(res as usize)
+            }
+        })
     }
 
     /// Like [`read`](Self::read), but async.
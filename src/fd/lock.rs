@@ -0,0 +1,161 @@
+//! Advisory record locks.
+
+use crate::errno::retry_on_interrupt;
+use crate::{Errno, Fd, Pid, Result};
+
+/// The kind of a record lock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LockKind {
+    /// A shared lock. Any number of processes can hold a read lock on the same region, as long
+    /// as no process holds a write lock on it.
+    ///
+    /// The file descriptor must be open for reading.
+    #[doc(alias = "F_RDLCK")]
+    Read,
+    /// An exclusive lock. A single process can hold a write lock on a region.
+    ///
+    /// The file descriptor must be open for writing.
+    #[doc(alias = "F_WRLCK")]
+    Write,
+}
+
+impl LockKind {
+    /// Returns the raw `l_type` value of this lock kind.
+    #[inline]
+    fn to_raw(self) -> libc::c_short {
+        match self {
+            Self::Read => libc::F_RDLCK as libc::c_short,
+            Self::Write => libc::F_WRLCK as libc::c_short,
+        }
+    }
+}
+
+/// Creates a `flock` structure describing `len` bytes starting at `start`.
+fn make_flock(kind: libc::c_short, start: u64, len: u64) -> libc::flock {
+    let mut lock: libc::flock = unsafe { core::mem::zeroed() };
+    lock.l_type = kind;
+    lock.l_whence = libc::SEEK_SET as libc::c_short;
+    lock.l_start = start as libc::off_t;
+    lock.l_len = len as libc::off_t;
+    lock
+}
+
+impl Fd {
+    /// Performs a `fcntl` call with the provided lock command.
+    fn fcntl_lock(self, cmd: libc::c_int, lock: &mut libc::flock) -> Result<()> {
+        let ret = unsafe { libc::fcntl(self.to_raw(), cmd, lock as *mut libc::flock) };
+        if ret < 0 {
+            Err(Errno::last())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Places an advisory lock on `len` bytes of the file, starting at offset `start`, waiting
+    /// for conflicting locks held by other processes to be released.
+    ///
+    /// A `len` of `0` extends the lock to the end of the file, however large it grows. Locking
+    /// a region that the process already holds a lock on replaces that lock.
+    ///
+    /// # Returns
+    ///
+    /// A guard that releases the lock when dropped.
+    ///
+    /// # Errors
+    ///
+    /// This function fails with [`Errno::DEADLK`] if waiting would cause a deadlock.
+    ///
+    /// # Notes
+    ///
+    /// Record locks are owned by the process, not by the file descriptor: closing *any* file
+    /// descriptor that refers to the file releases every lock the process holds on it, and
+    /// locks are not inherited by child processes.
+    #[doc(alias = "F_SETLKW")]
+    pub fn lock(self, kind: LockKind, start: u64, len: u64) -> Result<LockGuard> {
+        let mut lock = make_flock(kind.to_raw(), start, len);
+        retry_on_interrupt(|| self.fcntl_lock(libc::F_SETLKW, &mut lock))?;
+        Ok(LockGuard {
+            fd: self,
+            start,
+            len,
+        })
+    }
+
+    /// Like [`lock`](Self::lock), but fails instead of waiting when a conflicting lock is held
+    /// by another process.
+    ///
+    /// # Errors
+    ///
+    /// This function fails with [`Errno::AGAIN`] or [`Errno::ACCES`] (depending on the system)
+    /// if a conflicting lock is held.
+    #[doc(alias = "F_SETLK")]
+    pub fn try_lock(self, kind: LockKind, start: u64, len: u64) -> Result<LockGuard> {
+        let mut lock = make_flock(kind.to_raw(), start, len);
+        self.fcntl_lock(libc::F_SETLK, &mut lock)?;
+        Ok(LockGuard {
+            fd: self,
+            start,
+            len,
+        })
+    }
+
+    /// Returns the process that holds a lock preventing a lock of kind `kind` from being placed
+    /// on `len` bytes of the file, starting at offset `start`.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the lock could be placed. Locks held by the calling process never conflict.
+    #[doc(alias = "F_GETLK")]
+    pub fn lock_holder(self, kind: LockKind, start: u64, len: u64) -> Result<Option<Pid>> {
+        let mut lock = make_flock(kind.to_raw(), start, len);
+        self.fcntl_lock(libc::F_GETLK, &mut lock)?;
+
+        if lock.l_type == libc::F_UNLCK as libc::c_short {
+            Ok(None)
+        } else {
+            Ok(Some(Pid::from_raw(lock.l_pid)))
+        }
+    }
+}
+
+/// A guard that releases an advisory record lock when dropped.
+///
+/// This is returned by [`Fd::lock`] and [`Fd::try_lock`].
+#[derive(Debug)]
+#[must_use = "the lock is released as soon as the guard is dropped"]
+pub struct LockGuard {
+    /// The file descriptor the lock was placed through.
+    fd: Fd,
+    /// The start of the locked region.
+    start: u64,
+    /// The length of the locked region.
+    len: u64,
+}
+
+impl LockGuard {
+    /// Returns the file descriptor the lock was placed through.
+    #[inline]
+    pub fn fd(&self) -> Fd {
+        self.fd
+    }
+
+    /// Releases the lock, returning the error that might occur.
+    #[doc(alias = "F_UNLCK")]
+    pub fn unlock(self) -> Result<()> {
+        let ret = self.release();
+        core::mem::forget(self);
+        ret
+    }
+
+    /// Releases the lock.
+    fn release(&self) -> Result<()> {
+        let mut lock = make_flock(libc::F_UNLCK as libc::c_short, self.start, self.len);
+        self.fd.fcntl_lock(libc::F_SETLK, &mut lock)
+    }
+}
+
+impl Drop for LockGuard {
+    fn drop(&mut self) {
+        let _ = self.release();
+    }
+}
@@ -5,15 +5,45 @@ use core::ops::Deref;
 
 use crate::{Errno, Result};
 
+mod dup;
+#[cfg(target_os = "linux")]
+mod eventfd;
 mod fcntl;
 mod io;
+mod lock;
 mod net;
+mod pipe;
 mod poll;
+#[cfg(feature = "alloc")]
+mod poller;
+mod seek;
 mod select;
-
+#[cfg(target_os = "linux")]
+mod signalfd;
+#[cfg(target_os = "linux")]
+mod timerfd;
+#[cfg(target_os = "linux")]
+mod transfer;
+mod vectored;
+
+pub use self::dup::*;
+#[cfg(target_os = "linux")]
+pub use self::eventfd::*;
 pub use self::fcntl::*;
+pub use self::lock::*;
+pub use self::pipe::*;
 pub use self::poll::*;
+#[cfg(feature = "alloc")]
+pub use self::poller::*;
+pub use self::seek::*;
 pub use self::select::*;
+#[cfg(target_os = "linux")]
+pub use self::signalfd::*;
+#[cfg(target_os = "linux")]
+pub use self::timerfd::*;
+#[cfg(target_os = "linux")]
+pub use self::transfer::*;
+pub use self::vectored::*;
 
 /// A file descriptor.
 ///
@@ -33,6 +63,11 @@ impl Fd {
     pub const STDOUT: Self = Self(libc::STDOUT_FILENO);
     /// The file descriptor usually used to write data to the standard error stream.
     pub const STDERR: Self = Self(libc::STDERR_FILENO);
+    /// A special value that refers to the current working directory of the process.
+    ///
+    /// This can be passed to the `*_at` functions of [`ft::fs`](crate::fs).
+    #[doc(alias = "AT_FDCWD")]
+    pub const CWD: Self = Self(libc::AT_FDCWD);
 
     /// Creates a new [`Fd`] instance from the provided raw file descriptor.
     #[inline(always)]
@@ -68,11 +103,55 @@ impl Fd {
     pub fn is_a_tty(self) -> bool {
         unsafe { libc::isatty(self.0) == 1 }
     }
+
+    /// Reads a single value of type `T` from the file descriptor.
+    ///
+    /// This is meant for special files (such as event, timer or signal file descriptors) that
+    /// always produce whole records.
+    ///
+    /// # Safety
+    ///
+    /// Any sequence of bytes read from the file descriptor must be a valid `T`.
+    #[cfg(target_os = "linux")]
+    pub(crate) unsafe fn read_value<T>(self) -> Result<T> {
+        let mut value = core::mem::MaybeUninit::<T>::uninit();
+        let count = self.read(value_bytes(&mut value))?;
+        debug_assert_eq!(count, core::mem::size_of::<T>());
+        Ok(unsafe { value.assume_init() })
+    }
+
+    /// Like [`read_value`](Self::read_value), but schedules the current task to be woken up
+    /// when the file descriptor is ready for reading if the operation would block.
+    ///
+    /// # Safety
+    ///
+    /// Any sequence of bytes read from the file descriptor must be a valid `T`.
+    #[cfg(all(target_os = "linux", feature = "rt-single-thread"))]
+    pub(crate) unsafe fn poll_read_value<T>(
+        self,
+        cx: &mut core::task::Context,
+    ) -> core::task::Poll<Result<T>> {
+        let mut value = core::mem::MaybeUninit::<T>::uninit();
+        match core::task::ready!(self.poll_read(value_bytes(&mut value), cx)) {
+            Ok(count) => {
+                debug_assert_eq!(count, core::mem::size_of::<T>());
+                core::task::Poll::Ready(Ok(unsafe { value.assume_init() }))
+            }
+            Err(err) => core::task::Poll::Ready(Err(err)),
+        }
+    }
+}
+
+/// Returns the bytes of `value`, which may be uninitialized.
+#[cfg(target_os = "linux")]
+fn value_bytes<T>(value: &mut core::mem::MaybeUninit<T>) -> &mut [core::mem::MaybeUninit<u8>] {
+    unsafe { core::slice::from_raw_parts_mut(value.as_mut_ptr().cast(), core::mem::size_of::<T>()) }
 }
 
 /// A RAII wrapper around a file descriptor.
 ///
 /// When a [`File`] is dropped, the underlying file descriptor is automatically closed.
+#[derive(Debug)]
 pub struct File(Fd);
 
 impl File {
@@ -2,6 +2,7 @@
 
 use core::ffi::c_int;
 
+use crate::errno::retry_on_interrupt;
 #[cfg(feature = "futures")]
 use crate::futures;
 use crate::net::{SocketAddr, SocketAddrFamily, SocketType};
@@ -100,22 +101,26 @@ impl Fd {
         let mut addr_storage: libc::sockaddr_storage = unsafe { core::mem::zeroed() };
         let mut addr_len = core::mem::size_of::<libc::sockaddr_storage>() as libc::socklen_t;
 
-        let ret = unsafe {
-            libc::accept(
-                self.0,
-                &mut addr_storage as *mut libc::sockaddr_storage as *mut libc::sockaddr,
-                &mut addr_len,
-            )
-        };
-
-        if ret == -1 {
-            Err(Errno::last())
-        } else {
-            Ok((
-                File::from_raw(ret),
-                SocketAddr::from_raw(&addr_storage as *const _ as *const _),
-            ))
-        }
+        let ret = retry_on_interrupt(|| {
+            let ret = unsafe {
+                libc::accept(
+                    self.0,
+                    &mut addr_storage as *mut libc::sockaddr_storage as *mut libc::sockaddr,
+                    &mut addr_len,
+                )
+            };
+
+            if ret == -1 {
+                Err(Errno::last())
+            } else {
+                Ok(ret)
+            }
+        })?;
+
+        Ok((
+            File::from_raw(ret),
+            SocketAddr::from_raw(&addr_storage as *const _ as *const _),
+        ))
     }
 
     /// Like [`accept`](Self::accept), but returns a future that resolves when a connection is
@@ -0,0 +1,90 @@
+//! Anonymous pipes.
+
+use core::ffi::c_int;
+
+use bitflags::bitflags;
+
+use crate::{Errno, Fd, File, Result};
+
+bitflags! {
+    /// Flags that can be passed to [`Fd::pipe`].
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub struct PipeFlags: c_int {
+        /// Both ends of the pipe are closed automatically when the process calls `exec`.
+        const CLOEXEC = libc::O_CLOEXEC;
+        /// Both ends of the pipe are opened in non-blocking mode.
+        ///
+        /// This is required to use the ends of the pipe with the async functions of [`Fd`].
+        const NON_BLOCKING = libc::O_NONBLOCK;
+    }
+}
+
+impl Fd {
+    /// Creates an anonymous pipe.
+    ///
+    /// # Returns
+    ///
+    /// A `(read, write)` pair. Data written to the second file descriptor can be read from the
+    /// first one.
+    #[cfg(target_os = "linux")]
+    #[doc(alias = "pipe2")]
+    pub fn pipe(flags: PipeFlags) -> Result<(Fd, Fd)> {
+        let mut fds: [c_int; 2] = [0; 2];
+        let ret = unsafe { libc::pipe2(fds.as_mut_ptr(), flags.bits()) };
+        if ret == 0 {
+            Ok((Fd::from_raw(fds[0]), Fd::from_raw(fds[1])))
+        } else {
+            Err(Errno::last())
+        }
+    }
+
+    /// Creates an anonymous pipe.
+    ///
+    /// # Returns
+    ///
+    /// A `(read, write)` pair. Data written to the second file descriptor can be read from the
+    /// first one.
+    ///
+    /// # Remarks
+    ///
+    /// `pipe2` is not available on this platform. The flags are applied with `fcntl` once the
+    /// pipe is created, meaning that another thread may observe the file descriptors without
+    /// them.
+    #[cfg(not(target_os = "linux"))]
+    #[doc(alias = "pipe2")]
+    pub fn pipe(flags: PipeFlags) -> Result<(Fd, Fd)> {
+        let mut fds: [c_int; 2] = [0; 2];
+        let ret = unsafe { libc::pipe(fds.as_mut_ptr()) };
+        if ret != 0 {
+            return Err(Errno::last());
+        }
+
+        let (read, write) = (File::from_raw(fds[0]), File::from_raw(fds[1]));
+
+        for fd in [*read, *write] {
+            if flags.contains(PipeFlags::CLOEXEC)
+                && unsafe { libc::fcntl(fd.to_raw(), libc::F_SETFD, libc::FD_CLOEXEC) } < 0
+            {
+                return Err(Errno::last());
+            }
+
+            if flags.contains(PipeFlags::NON_BLOCKING) {
+                let fl = fd.get_flags()?;
+                fd.set_flags(fl | crate::fd::OpenFlags::NON_BLOCKING)?;
+            }
+        }
+
+        Ok((File::leak(read), File::leak(write)))
+    }
+}
+
+impl File {
+    /// Creates an anonymous pipe.
+    ///
+    /// See [`Fd::pipe`] for more information.
+    #[inline]
+    #[doc(alias = "pipe2")]
+    pub fn pipe(flags: PipeFlags) -> Result<(File, File)> {
+        Fd::pipe(flags).map(|(read, write)| (File::from_fd(read), File::from_fd(write)))
+    }
+}
@@ -5,6 +5,7 @@ use core::time::Duration;
 
 use bitflags::bitflags;
 
+use crate::errno::retry_on_interrupt;
 use crate::{Errno, Fd, Result};
 
 bitflags! {
@@ -79,6 +80,17 @@ impl PollFd {
     }
 }
 
+/// Converts an optional timeout to the number of milliseconds expected by `poll` and
+/// `epoll_wait`.
+///
+/// The timeout is rounded up, so that a short non-zero timeout does not turn into a
+/// non-blocking call. Timeouts that do not fit are treated as infinite.
+pub(crate) fn timeout_millis(timeout: Option<Duration>) -> c_int {
+    timeout
+        .and_then(|t| t.as_nanos().div_ceil(1_000_000).try_into().ok())
+        .unwrap_or(-1)
+}
+
 /// Waits for a collection of file descriptors to become ready.
 ///
 /// # Arguments
@@ -88,17 +100,22 @@ impl PollFd {
 ///   describes which events occurred on the file descriptors when the function returns.
 ///
 /// - `timeout` - The maximum amount of time to wait for an event to occur. If `None`, then
-///   the function will block indefinitely.
+///   the function will block indefinitely. The timeout is rounded up to the millisecond.
 pub fn poll(fds: &mut [PollFd], timeout: Option<Duration>) -> Result<usize> {
-    let timeout: c_int = timeout
-        .and_then(|t| t.as_millis().try_into().ok())
-        .unwrap_or(-1);
+    let millis = timeout_millis(timeout);
+    let mut poll_once = || {
+        let ret = unsafe { libc::poll(fds.as_mut_ptr().cast(), fds.len() as _, millis) };
 
-    let ret = unsafe { libc::poll(fds.as_mut_ptr().cast(), fds.len() as _, timeout) };
+        if ret < 0 {
+            Err(Errno::last())
+        } else {
+            Ok(ret as _)
+        }
+    };
 
-    if ret < 0 {
-        Err(Errno::last())
-    } else {
-        Ok(ret as _)
+    // Restarting a wait with the same timeout would make it last longer than requested.
+    match timeout {
+        None => retry_on_interrupt(poll_once),
+        Some(_) => poll_once(),
     }
 }
@@ -0,0 +1,447 @@
+//! Defines the [`Poller`] type.
+//!
+//! On Linux, the poller is backed by `epoll`. On other systems, and when the
+//! `restrict-functions` feature is enabled, it is implemented on top of [`poll`](super::poll)
+//! and exposes the same API.
+
+use core::time::Duration;
+
+use alloc::vec::Vec;
+use bitflags::bitflags;
+
+use crate::{Errno, Fd, Result};
+
+/// Whether the epoll backend is used.
+macro_rules! cfg_epoll {
+    ($($item:item)*) => {
+        $(
+            #[cfg(all(target_os = "linux", not(feature = "restrict-functions")))]
+            $item
+        )*
+    };
+}
+
+/// Whether the `poll` fallback is used.
+macro_rules! cfg_fallback {
+    ($($item:item)*) => {
+        $(
+            #[cfg(not(all(target_os = "linux", not(feature = "restrict-functions"))))]
+            $item
+        )*
+    };
+}
+
+bitflags! {
+    /// The events a [`Poller`] registration is interested in.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub struct Interest: u32 {
+        /// Report when the file descriptor can be read without blocking.
+        const READABLE = 1 << 0;
+        /// Report when the file descriptor can be written without blocking.
+        const WRITABLE = 1 << 1;
+        /// Report readiness only when it changes, instead of for as long as it lasts.
+        ///
+        /// The file descriptor must then be read or written until the operation fails with
+        /// [`Errno::WOULDBLOCK`] before waiting again.
+        ///
+        /// # Notes
+        ///
+        /// The `poll` fallback cannot observe readiness changes, and reports edge-triggered
+        /// registrations like level-triggered ones.
+        #[doc(alias = "EPOLLET")]
+        const EDGE_TRIGGERED = 1 << 2;
+    }
+}
+
+bitflags! {
+    /// The readiness of a file descriptor, as reported by an [`Event`].
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub struct Readiness: u32 {
+        /// The file descriptor can be read without blocking.
+        const READABLE = 1 << 0;
+        /// The file descriptor can be written without blocking.
+        const WRITABLE = 1 << 1;
+        /// An error occurred on the file descriptor.
+        const ERROR = 1 << 2;
+        /// The other end of the file descriptor has been closed.
+        const HANGUP = 1 << 3;
+    }
+}
+
+/// A readiness event reported by a [`Poller`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Event {
+    /// The token of the registration that became ready.
+    pub token: u64,
+    /// What the file descriptor is ready for.
+    pub readiness: Readiness,
+}
+
+/// A reusable buffer of [`Event`]s, filled by [`Poller::wait`].
+#[derive(Debug)]
+pub struct Events {
+    /// The events reported by the last wait.
+    list: Vec<Event>,
+    /// The maximum number of events reported by a single wait.
+    capacity: usize,
+}
+
+impl Events {
+    /// Creates a new [`Events`] buffer that can receive up to `capacity` events per wait.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `capacity` is zero.
+    pub fn with_capacity(capacity: usize) -> Result<Self> {
+        assert!(capacity != 0, "an event buffer cannot be empty");
+
+        let mut list = Vec::new();
+        list.try_reserve_exact(capacity).map_err(|_| Errno::NOMEM)?;
+        Ok(Self { list, capacity })
+    }
+
+    /// Returns the maximum number of events reported by a single wait.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of events reported by the last wait.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    /// Returns whether the last wait reported no events.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    /// Returns an iterator over the events reported by the last wait.
+    #[inline]
+    pub fn iter(&self) -> core::slice::Iter<'_, Event> {
+        self.list.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Events {
+    type Item = &'a Event;
+    type IntoIter = core::slice::Iter<'a, Event>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+cfg_epoll! {
+    use crate::errno::retry_on_interrupt;
+    use crate::File;
+    use super::poll::timeout_millis;
+
+    /// Waits for readiness events on a set of file descriptors.
+    ///
+    /// Each file descriptor is registered with an [`Interest`] and a user-defined token, which
+    /// is reported back in the [`Event`]s that concern it. Unlike [`poll`](super::poll) and
+    /// [`select`](super::select), the set of file descriptors is kept between waits and has no
+    /// size limit.
+    pub struct Poller {
+        /// The epoll instance.
+        epoll: File,
+        /// The buffer passed to `epoll_wait`.
+        raw: Vec<libc::epoll_event>,
+    }
+
+    impl Poller {
+        /// Creates a new, empty, [`Poller`].
+        #[doc(alias = "epoll_create1")]
+        pub fn new() -> Result<Self> {
+            let ret = unsafe { libc::epoll_create1(libc::EPOLL_CLOEXEC) };
+            if ret < 0 {
+                return Err(Errno::last());
+            }
+
+            Ok(Self {
+                epoll: File::from_raw(ret),
+                raw: Vec::new(),
+            })
+        }
+
+        /// Performs an `epoll_ctl` call.
+        fn ctl(&self, op: libc::c_int, fd: Fd, interest: Interest, token: u64) -> Result<()> {
+            let mut events = 0;
+            if interest.contains(Interest::READABLE) {
+                events |= libc::EPOLLIN | libc::EPOLLRDHUP;
+            }
+            if interest.contains(Interest::WRITABLE) {
+                events |= libc::EPOLLOUT;
+            }
+            if interest.contains(Interest::EDGE_TRIGGERED) {
+                events |= libc::EPOLLET;
+            }
+
+            let mut event = libc::epoll_event {
+                events: events as u32,
+                u64: token,
+            };
+
+            let ret = unsafe { libc::epoll_ctl(self.epoll.to_raw(), op, fd.to_raw(), &mut event) };
+            if ret < 0 {
+                Err(Errno::last())
+            } else {
+                Ok(())
+            }
+        }
+
+        /// Starts watching `fd` for the events described by `interest`.
+        ///
+        /// # Errors
+        ///
+        /// This function fails with [`Errno::EXIST`] if `fd` is already registered.
+        #[inline]
+        #[doc(alias = "EPOLL_CTL_ADD")]
+        pub fn add(&mut self, fd: Fd, interest: Interest, token: u64) -> Result<()> {
+            self.ctl(libc::EPOLL_CTL_ADD, fd, interest, token)
+        }
+
+        /// Changes the interest and the token of a registered file descriptor.
+        ///
+        /// # Errors
+        ///
+        /// This function fails with [`Errno::NOENT`] if `fd` is not registered.
+        #[inline]
+        #[doc(alias = "EPOLL_CTL_MOD")]
+        pub fn modify(&mut self, fd: Fd, interest: Interest, token: u64) -> Result<()> {
+            self.ctl(libc::EPOLL_CTL_MOD, fd, interest, token)
+        }
+
+        /// Stops watching `fd`.
+        ///
+        /// File descriptors should be removed before being closed.
+        ///
+        /// # Errors
+        ///
+        /// This function fails with [`Errno::NOENT`] if `fd` is not registered.
+        #[inline]
+        #[doc(alias = "EPOLL_CTL_DEL")]
+        pub fn remove(&mut self, fd: Fd) -> Result<()> {
+            self.ctl(libc::EPOLL_CTL_DEL, fd, Interest::empty(), 0)
+        }
+
+        /// Blocks until at least one registered file descriptor is ready, or until `timeout`
+        /// expires, and stores the resulting events in `events`.
+        ///
+        /// If `timeout` is `None`, this function blocks indefinitely. Otherwise, it is rounded up
+        /// to the millisecond.
+        ///
+        /// # Returns
+        ///
+        /// The number of events stored in `events`, which is `0` if the timeout expired.
+        #[doc(alias = "epoll_wait")]
+        pub fn wait(&mut self, events: &mut Events, timeout: Option<Duration>) -> Result<usize> {
+            events.list.clear();
+
+            if self.raw.capacity() < events.capacity {
+                self.raw
+                    .try_reserve_exact(events.capacity)
+                    .map_err(|_| Errno::NOMEM)?;
+            }
+
+            let mut wait_once = || {
+                let ret = unsafe {
+                    libc::epoll_wait(
+                        self.epoll.to_raw(),
+                        self.raw.as_mut_ptr(),
+                        events.capacity.min(libc::c_int::MAX as usize) as libc::c_int,
+                        timeout_millis(timeout),
+                    )
+                };
+                if ret < 0 {
+                    Err(Errno::last())
+                } else {
+                    Ok(ret)
+                }
+            };
+
+            // Restarting a wait with the same timeout would make it last longer than requested.
+            let ret = match timeout {
+                None => retry_on_interrupt(wait_once)?,
+                Some(_) => wait_once()?,
+            };
+
+            let raw = unsafe { core::slice::from_raw_parts(self.raw.as_ptr(), ret as usize) };
+            events.list.extend(raw.iter().map(|event| {
+                let flags = event.events as libc::c_int;
+                let mut readiness = Readiness::empty();
+                readiness.set(
+                    Readiness::READABLE,
+                    flags & (libc::EPOLLIN | libc::EPOLLPRI | libc::EPOLLRDHUP) != 0,
+                );
+                readiness.set(Readiness::WRITABLE, flags & libc::EPOLLOUT != 0);
+                readiness.set(Readiness::ERROR, flags & libc::EPOLLERR != 0);
+                readiness.set(
+                    Readiness::HANGUP,
+                    flags & (libc::EPOLLHUP | libc::EPOLLRDHUP) != 0,
+                );
+
+                Event {
+                    token: event.u64,
+                    readiness,
+                }
+            }));
+
+            Ok(events.list.len())
+        }
+    }
+}
+
+cfg_fallback! {
+    use super::{PollFd, PollFlags};
+
+    /// Waits for readiness events on a set of file descriptors.
+    ///
+    /// Each file descriptor is registered with an [`Interest`] and a user-defined token, which
+    /// is reported back in the [`Event`]s that concern it. Unlike [`poll`](super::poll) and
+    /// [`select`](super::select), the set of file descriptors is kept between waits and has no
+    /// size limit.
+    pub struct Poller {
+        /// The file descriptors passed to `poll`.
+        fds: Vec<PollFd>,
+        /// The tokens of the registrations, in the same order as `fds`.
+        tokens: Vec<u64>,
+        /// The index from which the next wait starts reporting events, so that file
+        /// descriptors at the end of the list are not starved when `Events` is too small.
+        cursor: usize,
+    }
+
+    impl Poller {
+        /// Creates a new, empty, [`Poller`].
+        pub fn new() -> Result<Self> {
+            Ok(Self {
+                fds: Vec::new(),
+                tokens: Vec::new(),
+                cursor: 0,
+            })
+        }
+
+        /// Returns the index of `fd` in the registration list.
+        fn position(&self, fd: Fd) -> Option<usize> {
+            self.fds.iter().position(|pollfd| pollfd.fd() == fd)
+        }
+
+        /// Converts an [`Interest`] to the events passed to `poll`.
+        fn poll_flags(interest: Interest) -> PollFlags {
+            let mut flags = PollFlags::empty();
+            if interest.contains(Interest::READABLE) {
+                flags |= PollFlags::IN;
+            }
+            if interest.contains(Interest::WRITABLE) {
+                flags |= PollFlags::OUT;
+            }
+            flags
+        }
+
+        /// Starts watching `fd` for the events described by `interest`.
+        ///
+        /// # Errors
+        ///
+        /// This function fails with [`Errno::EXIST`] if `fd` is already registered.
+        pub fn add(&mut self, fd: Fd, interest: Interest, token: u64) -> Result<()> {
+            if self.position(fd).is_some() {
+                return Err(Errno::EXIST);
+            }
+
+            self.fds.try_reserve(1).map_err(|_| Errno::NOMEM)?;
+            self.tokens.try_reserve(1).map_err(|_| Errno::NOMEM)?;
+            self.fds.push(PollFd::new(fd, Self::poll_flags(interest)));
+            self.tokens.push(token);
+            Ok(())
+        }
+
+        /// Changes the interest and the token of a registered file descriptor.
+        ///
+        /// # Errors
+        ///
+        /// This function fails with [`Errno::NOENT`] if `fd` is not registered.
+        pub fn modify(&mut self, fd: Fd, interest: Interest, token: u64) -> Result<()> {
+            let index = self.position(fd).ok_or(Errno::NOENT)?;
+            *self.fds[index].events_mut() = Self::poll_flags(interest);
+            self.tokens[index] = token;
+            Ok(())
+        }
+
+        /// Stops watching `fd`.
+        ///
+        /// File descriptors should be removed before being closed.
+        ///
+        /// # Errors
+        ///
+        /// This function fails with [`Errno::NOENT`] if `fd` is not registered.
+        pub fn remove(&mut self, fd: Fd) -> Result<()> {
+            let index = self.position(fd).ok_or(Errno::NOENT)?;
+            self.fds.swap_remove(index);
+            self.tokens.swap_remove(index);
+            Ok(())
+        }
+
+        /// Blocks until at least one registered file descriptor is ready, or until `timeout`
+        /// expires, and stores the resulting events in `events`.
+        ///
+        /// If `timeout` is `None`, this function blocks indefinitely. Otherwise, it is rounded up
+        /// to the millisecond.
+        ///
+        /// # Returns
+        ///
+        /// The number of events stored in `events`, which is `0` if the timeout expired.
+        pub fn wait(&mut self, events: &mut Events, timeout: Option<Duration>) -> Result<usize> {
+            events.list.clear();
+
+            let ready = super::poll(&mut self.fds, timeout)?;
+            if ready == 0 {
+                return Ok(0);
+            }
+
+            let len = self.fds.len();
+            let start = if self.cursor < len { self.cursor } else { 0 };
+
+            for offset in 0..len {
+                if events.list.len() == events.capacity {
+                    break;
+                }
+
+                let index = (start + offset) % len;
+                let flags = self.fds[index].revents();
+                if flags.is_empty() {
+                    continue;
+                }
+
+                let mut readiness = Readiness::empty();
+                readiness.set(
+                    Readiness::READABLE,
+                    flags.intersects(PollFlags::IN | PollFlags::PRI),
+                );
+                readiness.set(Readiness::WRITABLE, flags.contains(PollFlags::OUT));
+                readiness.set(
+                    Readiness::ERROR,
+                    flags.intersects(PollFlags::ERR | PollFlags::NVAL),
+                );
+                readiness.set(Readiness::HANGUP, flags.contains(PollFlags::HUP));
+
+                events.list.push(Event {
+                    token: self.tokens[index],
+                    readiness,
+                });
+                self.cursor = index + 1;
+            }
+
+            Ok(events.list.len())
+        }
+    }
+}
+
+impl core::fmt::Debug for Poller {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Poller").finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,114 @@
+//! Seeking and positional I/O.
+
+use core::ffi::c_void;
+use core::mem::MaybeUninit;
+
+use crate::errno::retry_on_interrupt;
+use crate::{Errno, Fd, Result};
+
+/// A position in a file, used by [`Fd::seek`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeekFrom {
+    /// An offset from the start of the file.
+    Start(u64),
+    /// An offset from the end of the file.
+    End(i64),
+    /// An offset from the current position in the file.
+    Current(i64),
+}
+
+impl Fd {
+    /// Moves the offset of this file descriptor.
+    ///
+    /// # Returns
+    ///
+    /// The new offset, from the start of the file.
+    #[doc(alias = "lseek")]
+    pub fn seek(self, pos: SeekFrom) -> Result<u64> {
+        let (offset, whence) = match pos {
+            SeekFrom::Start(offset) => (offset as libc::off_t, libc::SEEK_SET),
+            SeekFrom::End(offset) => (offset as libc::off_t, libc::SEEK_END),
+            SeekFrom::Current(offset) => (offset as libc::off_t, libc::SEEK_CUR),
+        };
+
+        let ret = unsafe { libc::lseek(self.to_raw(), offset, whence) };
+        if ret < 0 {
+            Err(Errno::last())
+        } else {
+            Ok(ret as u64)
+        }
+    }
+
+    /// Reads some amount of data from the file descriptor at the provided offset.
+    ///
+    /// Unlike [`read`](Self::read), this function does not use nor modify the offset of the file
+    /// descriptor.
+    ///
+    /// # Returns
+    ///
+    /// The number of bytes read, which may be less than the length of `buf`. `0` is returned when
+    /// `offset` is at or past the end of the file.
+    #[inline]
+    #[doc(alias = "pread")]
+    pub fn read_at(self, buf: &mut [MaybeUninit<u8>], offset: u64) -> Result<usize> {
+        retry_on_interrupt(|| {
+            let ret = unsafe {
+                libc::pread(
+                    self.to_raw(),
+                    buf.as_mut_ptr() as *mut c_void,
+                    buf.len(),
+                    offset as libc::off_t,
+                )
+            };
+
+            if ret < 0 {
+                Err(Errno::last())
+            } else {
+                Ok(ret as usize)
+            }
+        })
+    }
+
+    /// Writes some amount of the provided buffer to the file descriptor at the provided offset.
+    ///
+    /// Unlike [`write`](Self::write), this function does not use nor modify the offset of the
+    /// file descriptor.
+    ///
+    /// # Returns
+    ///
+    /// The number of bytes written, which may be less than the length of `data`.
+    #[inline]
+    #[doc(alias = "pwrite")]
+    pub fn write_at(self, data: &[u8], offset: u64) -> Result<usize> {
+        retry_on_interrupt(|| {
+            let ret = unsafe {
+                libc::pwrite(
+                    self.to_raw(),
+                    data.as_ptr() as *const c_void,
+                    data.len(),
+                    offset as libc::off_t,
+                )
+            };
+
+            if ret < 0 {
+                Err(Errno::last())
+            } else {
+                Ok(ret as usize)
+            }
+        })
+    }
+
+    /// Truncates or extends the file to exactly `len` bytes.
+    ///
+    /// When the file is extended, the new part reads as zeros.
+    #[inline]
+    #[doc(alias = "ftruncate")]
+    pub fn truncate(self, len: u64) -> Result<()> {
+        let ret = unsafe { libc::ftruncate(self.to_raw(), len as libc::off_t) };
+        if ret == 0 {
+            Ok(())
+        } else {
+            Err(Errno::last())
+        }
+    }
+}
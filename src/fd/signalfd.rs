@@ -0,0 +1,166 @@
+//! Signal file descriptors.
+
+use core::ffi::c_int;
+use core::ops::Deref;
+
+use bitflags::bitflags;
+
+#[cfg(feature = "futures")]
+use crate::futures;
+use crate::{Errno, Fd, File, Pid, Result, Signal, Uid};
+
+bitflags! {
+    /// Flags that can be passed to [`SignalFd::new`].
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub struct SignalFdFlags: c_int {
+        /// The file descriptor is closed automatically when the process calls `exec`.
+        const CLOEXEC = libc::SFD_CLOEXEC;
+        /// The file descriptor is opened in non-blocking mode.
+        ///
+        /// This is required to use [`SignalFd::async_read`].
+        const NON_BLOCKING = libc::SFD_NONBLOCK;
+    }
+}
+
+/// Information about a signal received through a [`SignalFd`].
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+#[doc(alias = "signalfd_siginfo")]
+pub struct SigInfo(libc::signalfd_siginfo);
+
+impl SigInfo {
+    /// Returns the underlying `signalfd_siginfo` structure.
+    #[inline]
+    pub fn as_raw(&self) -> &libc::signalfd_siginfo {
+        &self.0
+    }
+
+    /// Returns the signal that was received.
+    #[inline]
+    pub fn signal(&self) -> Signal {
+        Signal::from_raw(self.0.ssi_signo as c_int)
+    }
+
+    /// Returns the code that describes why the signal was sent (the `si_code` field).
+    #[inline]
+    pub fn code(&self) -> i32 {
+        self.0.ssi_code
+    }
+
+    /// Returns the process that sent the signal.
+    ///
+    /// This is only meaningful for signals sent with `kill` or `sigqueue`, and for `SIGCHLD`,
+    /// where it is the child that changed state.
+    #[inline]
+    pub fn pid(&self) -> Pid {
+        Pid::from_raw(self.0.ssi_pid as libc::pid_t)
+    }
+
+    /// Returns the real user ID of the process that sent the signal.
+    #[inline]
+    pub fn uid(&self) -> Uid {
+        Uid::from_raw(self.0.ssi_uid)
+    }
+
+    /// Returns the exit status or signal of the child, for `SIGCHLD`.
+    #[inline]
+    pub fn status(&self) -> i32 {
+        self.0.ssi_status
+    }
+}
+
+impl core::fmt::Debug for SigInfo {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SigInfo")
+            .field("signal", &self.signal())
+            .field("code", &self.code())
+            .field("pid", &self.pid())
+            .field("uid", &self.uid())
+            .field("status", &self.status())
+            .finish()
+    }
+}
+
+/// A file descriptor from which signals sent to the process can be read.
+///
+/// The file descriptor is readable whenever one of its signals is pending, meaning that it can
+/// be registered with [`poll`](crate::fd::poll) or a [`Poller`](crate::fd::Poller) like any
+/// other file descriptor. It is closed when the [`SignalFd`] is dropped.
+#[derive(Debug)]
+#[doc(alias = "signalfd")]
+pub struct SignalFd(File);
+
+impl SignalFd {
+    /// Creates a new [`SignalFd`] that receives the provided signals.
+    ///
+    /// The signals are added to the signal mask of the calling thread, so that they are not
+    /// delivered to their usual handlers anymore. They remain blocked when the [`SignalFd`] is
+    /// dropped, but the previous signal mask is restored if the file descriptor cannot be
+    /// created.
+    ///
+    /// # Remarks
+    ///
+    /// In multi-threaded programs, the signals should be blocked in every thread (typically by
+    /// creating the [`SignalFd`] before spawning any thread), or they may be delivered to a
+    /// thread that does not block them.
+    pub fn new(signals: &[Signal], flags: SignalFdFlags) -> Result<Self> {
+        let mut set = unsafe { core::mem::zeroed() };
+        unsafe { libc::sigemptyset(&mut set) };
+        for signal in signals {
+            if unsafe { libc::sigaddset(&mut set, signal.as_raw()) } < 0 {
+                return Err(Errno::last());
+            }
+        }
+
+        let mut old_set = unsafe { core::mem::zeroed() };
+        let ret = unsafe { libc::pthread_sigmask(libc::SIG_BLOCK, &set, &mut old_set) };
+        if ret != 0 {
+            return Err(Errno::from_raw(ret));
+        }
+
+        let ret = unsafe { libc::signalfd(-1, &set, flags.bits()) };
+        if ret < 0 {
+            let err = Errno::last();
+            // Nothing would receive the signals anymore.
+            unsafe { libc::pthread_sigmask(libc::SIG_SETMASK, &old_set, core::ptr::null_mut()) };
+            Err(err)
+        } else {
+            Ok(Self(File::from_raw(ret)))
+        }
+    }
+
+    /// Waits for one of the signals to be received.
+    #[inline]
+    pub fn read(&self) -> Result<SigInfo> {
+        unsafe { self.0.read_value() }
+    }
+
+    /// Attempts to read a pending signal.
+    ///
+    /// If none of the signals is pending, this function returns `Pending` and schedules the
+    /// current task to be woken up when one is.
+    #[inline]
+    #[cfg(feature = "rt-single-thread")]
+    pub fn poll_read(&self, cx: &mut core::task::Context) -> core::task::Poll<Result<SigInfo>> {
+        unsafe { self.0.poll_read_value(cx) }
+    }
+
+    /// Like [`read`](Self::read), but asynchronous.
+    ///
+    /// The file descriptor must have been created with
+    /// [`NON_BLOCKING`](SignalFdFlags::NON_BLOCKING).
+    #[inline]
+    #[cfg(feature = "futures")]
+    pub fn async_read(&self) -> futures::NextSignal<'_> {
+        futures::NextSignal { signals: self }
+    }
+}
+
+impl Deref for SignalFd {
+    type Target = Fd;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
@@ -0,0 +1,197 @@
+//! Timer file descriptors.
+
+use core::ffi::c_int;
+use core::ops::Deref;
+use core::time::Duration;
+
+use bitflags::bitflags;
+
+#[cfg(feature = "futures")]
+use crate::futures;
+use crate::{Clock, Errno, Fd, File, Instant, Result};
+
+bitflags! {
+    /// Flags that can be passed to [`TimerFd::new`].
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub struct TimerFdFlags: c_int {
+        /// The file descriptor is closed automatically when the process calls `exec`.
+        const CLOEXEC = libc::TFD_CLOEXEC;
+        /// The file descriptor is opened in non-blocking mode.
+        ///
+        /// This is required to use [`TimerFd::async_wait`].
+        const NON_BLOCKING = libc::TFD_NONBLOCK;
+    }
+}
+
+/// Converts a [`Duration`] to a `timespec`.
+fn to_timespec(duration: Duration) -> libc::timespec {
+    libc::timespec {
+        tv_sec: duration.as_secs().min(libc::time_t::MAX as u64) as libc::time_t,
+        tv_nsec: duration.subsec_nanos() as _,
+    }
+}
+
+/// Converts a `timespec` to a [`Duration`].
+fn from_timespec(timespec: libc::timespec) -> Duration {
+    Duration::new(timespec.tv_sec as u64, timespec.tv_nsec as u32)
+}
+
+/// A timer that notifies expirations through a file descriptor.
+///
+/// The file descriptor is readable once the timer has expired, meaning that it can be
+/// registered with [`poll`](crate::fd::poll) or a [`Poller`](crate::fd::Poller) like any other
+/// file descriptor. It is closed when the [`TimerFd`] is dropped.
+///
+/// A new timer is disarmed.
+#[derive(Debug)]
+#[doc(alias = "timerfd")]
+pub struct TimerFd {
+    /// The timer file descriptor.
+    file: File,
+    /// The clock the timer was created with.
+    clock: Clock,
+}
+
+impl TimerFd {
+    /// Creates a new, disarmed, [`TimerFd`] that measures time with `clock`.
+    #[doc(alias = "timerfd_create")]
+    pub fn new(clock: Clock, flags: TimerFdFlags) -> Result<Self> {
+        let ret = unsafe { libc::timerfd_create(clock.as_raw(), flags.bits()) };
+        if ret < 0 {
+            Err(Errno::last())
+        } else {
+            Ok(Self {
+                file: File::from_raw(ret),
+                clock,
+            })
+        }
+    }
+
+    /// Returns the clock the timer measures time with.
+    #[inline]
+    pub fn clock(&self) -> Clock {
+        self.clock
+    }
+
+    /// Arms or disarms the timer.
+    fn settime(&self, flags: c_int, value: Duration, interval: Duration) -> Result<()> {
+        let spec = libc::itimerspec {
+            it_interval: to_timespec(interval),
+            it_value: to_timespec(value),
+        };
+
+        let ret = unsafe {
+            libc::timerfd_settime(self.file.to_raw(), flags, &spec, core::ptr::null_mut())
+        };
+        if ret < 0 {
+            Err(Errno::last())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Arms the timer to expire once, after `delay`.
+    ///
+    /// This replaces any previous setting of the timer.
+    #[doc(alias = "timerfd_settime")]
+    pub fn set_after(&self, delay: Duration) -> Result<()> {
+        // A zero value would disarm the timer instead of making it expire immediately.
+        self.settime(0, delay.max(Duration::from_nanos(1)), Duration::ZERO)
+    }
+
+    /// Arms the timer to expire once, when the clock of the timer reaches `at`.
+    ///
+    /// An instant in the past makes the timer expire immediately. This replaces any previous
+    /// setting of the timer.
+    #[doc(alias = "timerfd_settime")]
+    pub fn set_at(&self, at: Instant) -> Result<()> {
+        self.settime(
+            libc::TFD_TIMER_ABSTIME,
+            at.0.max(Duration::from_nanos(1)),
+            Duration::ZERO,
+        )
+    }
+
+    /// Arms the timer to expire after `first`, and then every `interval`.
+    ///
+    /// This replaces any previous setting of the timer.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `interval` is zero.
+    #[track_caller]
+    #[doc(alias = "timerfd_settime")]
+    pub fn set_interval(&self, first: Duration, interval: Duration) -> Result<()> {
+        assert!(
+            !interval.is_zero(),
+            "the interval of a timer cannot be zero"
+        );
+        self.settime(0, first.max(Duration::from_nanos(1)), interval)
+    }
+
+    /// Disarms the timer.
+    #[inline]
+    #[doc(alias = "timerfd_settime")]
+    pub fn disarm(&self) -> Result<()> {
+        self.settime(0, Duration::ZERO, Duration::ZERO)
+    }
+
+    /// Returns the time remaining until the next expiration of the timer.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the timer is disarmed.
+    #[doc(alias = "timerfd_gettime")]
+    pub fn remaining(&self) -> Result<Option<Duration>> {
+        let mut spec = unsafe { core::mem::zeroed() };
+        let ret = unsafe { libc::timerfd_gettime(self.file.to_raw(), &mut spec) };
+        if ret < 0 {
+            return Err(Errno::last());
+        }
+
+        match from_timespec(spec.it_value) {
+            Duration::ZERO => Ok(None),
+            remaining => Ok(Some(remaining)),
+        }
+    }
+
+    /// Waits for the timer to expire.
+    ///
+    /// # Returns
+    ///
+    /// The number of expirations since the timer was set or since the last wait, which can be
+    /// more than one for periodic timers.
+    #[inline]
+    pub fn wait(&self) -> Result<u64> {
+        unsafe { self.file.read_value() }
+    }
+
+    /// Attempts to consume the expirations of the timer.
+    ///
+    /// If the timer has not expired, this function returns `Pending` and schedules the current
+    /// task to be woken up when it does.
+    #[inline]
+    #[cfg(feature = "rt-single-thread")]
+    pub fn poll_wait(&self, cx: &mut core::task::Context) -> core::task::Poll<Result<u64>> {
+        unsafe { self.file.poll_read_value(cx) }
+    }
+
+    /// Like [`wait`](Self::wait), but asynchronous.
+    ///
+    /// The file descriptor must have been created with
+    /// [`NON_BLOCKING`](TimerFdFlags::NON_BLOCKING).
+    #[inline]
+    #[cfg(feature = "futures")]
+    pub fn async_wait(&self) -> futures::Expirations<'_> {
+        futures::Expirations { timer: self }
+    }
+}
+
+impl Deref for TimerFd {
+    type Target = Fd;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.file
+    }
+}
@@ -0,0 +1,215 @@
+//! Transfers of data between file descriptors without going through user space.
+
+use core::ffi::c_uint;
+
+use bitflags::bitflags;
+
+#[cfg(feature = "futures")]
+use crate::futures;
+use crate::{Errno, Fd, Result};
+
+bitflags! {
+    /// Flags that can be passed to [`Fd::splice`] and [`Fd::tee`].
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub struct SpliceFlags: c_uint {
+        /// Attempt to move pages instead of copying them. This is only a hint to the kernel.
+        const MOVE = libc::SPLICE_F_MOVE;
+        /// Do not block on the pipes. The other file descriptor may still block, depending on
+        /// its own flags.
+        const NON_BLOCKING = libc::SPLICE_F_NONBLOCK;
+        /// More data will be sent in a subsequent call. This is a hint for sockets.
+        const MORE = libc::SPLICE_F_MORE;
+    }
+}
+
+/// Returns the value of an optional offset, as expected by the system calls of this module.
+///
+/// The value updated by the kernel must be written back with [`store_offset`].
+fn load_offset(offset: &Option<&mut u64>) -> libc::loff_t {
+    offset
+        .as_deref()
+        .map_or(0, |&offset| offset as libc::loff_t)
+}
+
+/// Writes an offset updated by the kernel back to `offset`.
+fn store_offset(offset: Option<&mut u64>, value: libc::loff_t) {
+    if let Some(offset) = offset {
+        *offset = value as u64;
+    }
+}
+
+/// Returns a pointer to `value` if `offset` is provided, or a null pointer otherwise.
+fn offset_ptr(offset: &Option<&mut u64>, value: &mut libc::loff_t) -> *mut libc::loff_t {
+    if offset.is_some() {
+        value
+    } else {
+        core::ptr::null_mut()
+    }
+}
+
+impl Fd {
+    /// Copies up to `len` bytes from this file descriptor to `out`, without copying them to
+    /// user space.
+    ///
+    /// This file descriptor must support `mmap`-like operations (a regular file, typically),
+    /// and `out` is usually a socket.
+    ///
+    /// If `offset` is provided, data is read starting from that offset, which is then updated
+    /// to point after the last byte read, and the file offset of this file descriptor is left
+    /// unchanged. Otherwise, data is read from the file offset, which is updated.
+    ///
+    /// # Returns
+    ///
+    /// The number of bytes transferred, which may be less than `len`. `0` means that the end
+    /// of the file was reached.
+    #[doc(alias = "sendfile")]
+    #[doc(alias = "sendfile64")]
+    pub fn send_file_to(self, out: Fd, offset: Option<&mut u64>, len: usize) -> Result<usize> {
+        let mut value = load_offset(&offset);
+
+        let ret = unsafe {
+            libc::sendfile64(
+                out.to_raw(),
+                self.to_raw(),
+                offset_ptr(&offset, &mut value),
+                len,
+            )
+        };
+        if ret < 0 {
+            return Err(Errno::last());
+        }
+
+        store_offset(offset, value);
+        Ok(ret as usize)
+    }
+
+    /// Like [`send_file_to`](Self::send_file_to), but transfers exactly `len` bytes starting
+    /// at `offset`, or until the end of the file is reached.
+    ///
+    /// This function assumes that `out` is non-blocking.
+    #[inline]
+    #[cfg(feature = "futures")]
+    #[doc(alias = "sendfile")]
+    pub fn async_send_file_to(self, out: Fd, offset: u64, len: usize) -> futures::SendFile {
+        futures::SendFile::new(self, out, offset, len)
+    }
+
+    /// Attempts to transfer data with [`send_file_to`](Self::send_file_to).
+    ///
+    /// If `out` is not ready for writing, this function returns `Pending` and schedules the
+    /// current task to be woken up when it is.
+    #[cfg(feature = "rt-single-thread")]
+    pub fn poll_send_file_to(
+        self,
+        out: Fd,
+        offset: Option<&mut u64>,
+        len: usize,
+        cx: &mut core::task::Context,
+    ) -> core::task::Poll<Result<usize>> {
+        match self.send_file_to(out, offset, len) {
+            Ok(count) => core::task::Poll::Ready(Ok(count)),
+            Err(Errno::WOULDBLOCK) => {
+                match crate::runtime::wake_me_up_on_io(
+                    crate::fd::PollFd::new(out, crate::fd::PollFlags::OUT),
+                    cx.waker().clone(),
+                ) {
+                    Ok(()) => core::task::Poll::Pending,
+                    Err(err) => core::task::Poll::Ready(Err(err.into())),
+                }
+            }
+            Err(err) => core::task::Poll::Ready(Err(err)),
+        }
+    }
+
+    /// Moves up to `len` bytes from this file descriptor to `out`, without copying them to user
+    /// space.
+    ///
+    /// One of the two file descriptors must be a pipe. The offsets must be `None` for pipes;
+    /// for other files, they behave like the offset of [`send_file_to`](Self::send_file_to).
+    ///
+    /// # Returns
+    ///
+    /// The number of bytes moved. `0` means that there was no data to move, and that no
+    /// writer has this pipe open.
+    pub fn splice(
+        self,
+        offset_in: Option<&mut u64>,
+        out: Fd,
+        offset_out: Option<&mut u64>,
+        len: usize,
+        flags: SpliceFlags,
+    ) -> Result<usize> {
+        let mut value_in = load_offset(&offset_in);
+        let mut value_out = load_offset(&offset_out);
+
+        let ret = unsafe {
+            libc::splice(
+                self.to_raw(),
+                offset_ptr(&offset_in, &mut value_in),
+                out.to_raw(),
+                offset_ptr(&offset_out, &mut value_out),
+                len,
+                flags.bits(),
+            )
+        };
+        if ret < 0 {
+            return Err(Errno::last());
+        }
+
+        store_offset(offset_in, value_in);
+        store_offset(offset_out, value_out);
+        Ok(ret as usize)
+    }
+
+    /// Duplicates up to `len` bytes from this pipe to the pipe `out`, without consuming them.
+    ///
+    /// # Returns
+    ///
+    /// The number of bytes duplicated. `0` means that there was no data to duplicate.
+    #[inline]
+    pub fn tee(self, out: Fd, len: usize, flags: SpliceFlags) -> Result<usize> {
+        let ret = unsafe { libc::tee(self.to_raw(), out.to_raw(), len, flags.bits()) };
+        if ret < 0 {
+            Err(Errno::last())
+        } else {
+            Ok(ret as usize)
+        }
+    }
+
+    /// Copies up to `len` bytes from this file to the file `out`, possibly without reading
+    /// them at all (on file systems that support reflinks, for example).
+    ///
+    /// The offsets behave like the offset of [`send_file_to`](Self::send_file_to).
+    ///
+    /// # Returns
+    ///
+    /// The number of bytes copied. `0` means that the end of the input file was reached.
+    pub fn copy_file_range(
+        self,
+        offset_in: Option<&mut u64>,
+        out: Fd,
+        offset_out: Option<&mut u64>,
+        len: usize,
+    ) -> Result<usize> {
+        let mut value_in = load_offset(&offset_in);
+        let mut value_out = load_offset(&offset_out);
+
+        let ret = unsafe {
+            libc::copy_file_range(
+                self.to_raw(),
+                offset_ptr(&offset_in, &mut value_in),
+                out.to_raw(),
+                offset_ptr(&offset_out, &mut value_out),
+                len,
+                0,
+            )
+        };
+        if ret < 0 {
+            return Err(Errno::last());
+        }
+
+        store_offset(offset_in, value_in);
+        store_offset(offset_out, value_out);
+        Ok(ret as usize)
+    }
+}
@@ -0,0 +1,312 @@
+//! Vectored reads and writes.
+
+use core::fmt;
+use core::marker::PhantomData;
+use core::mem::MaybeUninit;
+use core::ops::{Deref, DerefMut};
+
+use crate::errno::retry_on_interrupt;
+#[cfg(feature = "futures")]
+use crate::futures;
+use crate::{Errno, Fd, Result};
+
+/// The maximum number of buffers that can be passed to `readv` and `writev` at once.
+///
+/// This is the value of `IOV_MAX` on Linux and macOS. Additional buffers are ignored, which is
+/// allowed because those functions may transfer less data than requested.
+const IOV_MAX: usize = 1024;
+
+/// A buffer to write data from, used by [`Fd::write_vectored`].
+///
+/// This type is guaranteed to have the same layout as a C `struct iovec`.
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+#[doc(alias = "iovec")]
+pub struct IoSlice<'a>(libc::iovec, PhantomData<&'a [u8]>);
+
+unsafe impl Send for IoSlice<'_> {}
+unsafe impl Sync for IoSlice<'_> {}
+
+impl<'a> IoSlice<'a> {
+    /// Creates a new [`IoSlice`] referencing the provided buffer.
+    #[inline]
+    pub const fn new(buf: &'a [u8]) -> Self {
+        Self(
+            libc::iovec {
+                iov_base: buf.as_ptr() as *mut _,
+                iov_len: buf.len(),
+            },
+            PhantomData,
+        )
+    }
+
+    /// Returns the referenced buffer, with the lifetime of the original buffer.
+    #[inline]
+    pub const fn as_slice(&self) -> &'a [u8] {
+        unsafe { core::slice::from_raw_parts(self.0.iov_base as *const u8, self.0.iov_len) }
+    }
+
+    /// Skips the first `n` bytes of this buffer.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `n` is larger than the length of the buffer.
+    #[inline]
+    pub fn advance(&mut self, n: usize) {
+        *self = Self::new(&self.as_slice()[n..]);
+    }
+
+    /// Skips the first `n` bytes of a list of buffers.
+    ///
+    /// Buffers that are entirely skipped are removed from the list, and the first remaining
+    /// buffer is advanced by the number of bytes that remain to be skipped.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `n` is larger than the total length of the buffers.
+    pub fn advance_slices(bufs: &mut &mut [IoSlice<'a>], n: usize) {
+        let mut left = n;
+        let mut remove = 0;
+
+        for buf in bufs.iter() {
+            if buf.len() > left {
+                break;
+            }
+            left -= buf.len();
+            remove += 1;
+        }
+
+        *bufs = &mut core::mem::take(bufs)[remove..];
+
+        match bufs.first_mut() {
+            Some(first) => first.advance(left),
+            None => assert!(left == 0, "advancing past the end of the buffers"),
+        }
+    }
+}
+
+impl Deref for IoSlice<'_> {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl fmt::Debug for IoSlice<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(crate::utils::display_bytes(self), f)
+    }
+}
+
+/// A buffer to read data into, used by [`Fd::read_vectored`].
+///
+/// This type is guaranteed to have the same layout as a C `struct iovec`.
+#[repr(transparent)]
+#[doc(alias = "iovec")]
+pub struct IoSliceMut<'a>(libc::iovec, PhantomData<&'a mut [MaybeUninit<u8>]>);
+
+unsafe impl Send for IoSliceMut<'_> {}
+unsafe impl Sync for IoSliceMut<'_> {}
+
+impl<'a> IoSliceMut<'a> {
+    /// Creates a new [`IoSliceMut`] referencing the provided buffer.
+    #[inline]
+    pub fn new(buf: &'a mut [MaybeUninit<u8>]) -> Self {
+        Self(
+            libc::iovec {
+                iov_base: buf.as_mut_ptr() as *mut _,
+                iov_len: buf.len(),
+            },
+            PhantomData,
+        )
+    }
+}
+
+impl Deref for IoSliceMut<'_> {
+    type Target = [MaybeUninit<u8>];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        unsafe { core::slice::from_raw_parts(self.0.iov_base as *const _, self.0.iov_len) }
+    }
+}
+
+impl DerefMut for IoSliceMut<'_> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { core::slice::from_raw_parts_mut(self.0.iov_base as *mut _, self.0.iov_len) }
+    }
+}
+
+impl fmt::Debug for IoSliceMut<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IoSliceMut")
+            .field("len", &self.0.iov_len)
+            .finish()
+    }
+}
+
+impl Fd {
+    /// Writes some amount of data from the provided buffers to the file descriptor, in order.
+    ///
+    /// # Returns
+    ///
+    /// The total number of bytes written, which may be less than the total length of the
+    /// buffers. At most 1024 buffers are written at once.
+    #[inline]
+    #[doc(alias = "writev")]
+    pub fn write_vectored(self, bufs: &[IoSlice]) -> Result<usize> {
+        retry_on_interrupt(|| {
+            let ret = unsafe {
+                libc::writev(
+                    self.to_raw(),
+                    bufs.as_ptr() as *const libc::iovec,
+                    bufs.len().min(IOV_MAX) as _,
+                )
+            };
+
+            if ret < 0 {
+                Err(Errno::last())
+            } else {
+                Ok(ret as usize)
+            }
+        })
+    }
+
+    /// Like [`write_vectored`](Self::write_vectored), but async.
+    #[inline]
+    #[cfg(feature = "futures")]
+    #[doc(alias = "writev")]
+    pub fn async_write_vectored<'a, 'b>(
+        self,
+        bufs: &'a [IoSlice<'b>],
+    ) -> futures::WriteVectored<'a, 'b> {
+        futures::WriteVectored { fd: self, bufs }
+    }
+
+    /// Writes the entire contents of the provided buffers to the file descriptor.
+    ///
+    /// This function calls [`write_vectored`](Self::write_vectored) in a loop, advancing `bufs`
+    /// past the data that was written each time. When the function returns, `bufs` may have been
+    /// modified.
+    ///
+    /// # Errors
+    ///
+    /// If the file descriptor stops accepting data before everything has been written, this
+    /// function fails with [`Errno::NOSPC`].
+    #[doc(alias = "writev")]
+    pub fn write_all_vectored(self, mut bufs: &mut [IoSlice]) -> Result<()> {
+        IoSlice::advance_slices(&mut bufs, 0);
+
+        while !bufs.is_empty() {
+            match self.write_vectored(bufs)? {
+                // Nothing can be written anymore, which would make the loop spin forever.
+                0 => return Err(Errno::NOSPC),
+                n => IoSlice::advance_slices(&mut bufs, n),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Like [`write_all_vectored`](Self::write_all_vectored), but async.
+    #[inline]
+    #[cfg(feature = "futures")]
+    #[doc(alias = "writev")]
+    pub fn async_write_all_vectored<'a, 'b>(
+        self,
+        bufs: &'a mut [IoSlice<'b>],
+    ) -> futures::WriteAllVectored<'a, 'b> {
+        futures::WriteAllVectored { fd: self, bufs }
+    }
+
+    /// Reads some amount of data from the file descriptor into the provided buffers, filling
+    /// them in order.
+    ///
+    /// # Returns
+    ///
+    /// The total number of bytes read. `0` is returned when the end of the file has been
+    /// reached. At most 1024 buffers are filled at once.
+    #[inline]
+    #[doc(alias = "readv")]
+    pub fn read_vectored(self, bufs: &mut [IoSliceMut]) -> Result<usize> {
+        retry_on_interrupt(|| {
+            let ret = unsafe {
+                libc::readv(
+                    self.to_raw(),
+                    bufs.as_ptr() as *const libc::iovec,
+                    bufs.len().min(IOV_MAX) as _,
+                )
+            };
+
+            if ret < 0 {
+                Err(Errno::last())
+            } else {
+                Ok(ret as usize)
+            }
+        })
+    }
+
+    /// Like [`read_vectored`](Self::read_vectored), but async.
+    #[inline]
+    #[cfg(feature = "futures")]
+    #[doc(alias = "readv")]
+    pub fn async_read_vectored<'a, 'b>(
+        self,
+        bufs: &'a mut [IoSliceMut<'b>],
+    ) -> futures::ReadVectored<'a, 'b> {
+        futures::ReadVectored { fd: self, bufs }
+    }
+
+    /// Attempts to perform a vectored write on this file descriptor.
+    ///
+    /// If the operation would block, this function returns `Pending` and schedules the current
+    /// task to be woken up when the file descriptor is ready for writing.
+    #[cfg(feature = "rt-single-thread")]
+    pub fn poll_write_vectored(
+        self,
+        bufs: &[IoSlice],
+        cx: &mut core::task::Context,
+    ) -> core::task::Poll<Result<usize>> {
+        match self.write_vectored(bufs) {
+            Ok(count) => core::task::Poll::Ready(Ok(count)),
+            Err(Errno::WOULDBLOCK) => {
+                match crate::runtime::wake_me_up_on_io(
+                    crate::fd::poll::PollFd::new(self, crate::fd::poll::PollFlags::OUT),
+                    cx.waker().clone(),
+                ) {
+                    Ok(()) => core::task::Poll::Pending,
+                    Err(err) => core::task::Poll::Ready(Err(err.into())),
+                }
+            }
+            Err(err) => core::task::Poll::Ready(Err(err)),
+        }
+    }
+
+    /// Attempts to perform a vectored read on this file descriptor.
+    ///
+    /// If the operation would block, this function returns `Pending` and schedules the current
+    /// task to be woken up when the file descriptor is ready for reading.
+    #[cfg(feature = "rt-single-thread")]
+    pub fn poll_read_vectored(
+        self,
+        bufs: &mut [IoSliceMut],
+        cx: &mut core::task::Context,
+    ) -> core::task::Poll<Result<usize>> {
+        match self.read_vectored(bufs) {
+            Ok(count) => core::task::Poll::Ready(Ok(count)),
+            Err(Errno::WOULDBLOCK) => {
+                match crate::runtime::wake_me_up_on_io(
+                    crate::fd::poll::PollFd::new(self, crate::fd::poll::PollFlags::IN),
+                    cx.waker().clone(),
+                ) {
+                    Ok(()) => core::task::Poll::Pending,
+                    Err(err) => core::task::Poll::Ready(Err(err.into())),
+                }
+            }
+            Err(err) => core::task::Poll::Ready(Err(err)),
+        }
+    }
+}
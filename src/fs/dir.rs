@@ -0,0 +1,283 @@
+//! Defines the [`Dir`] type.
+
+use core::ffi::c_void;
+
+use super::FileType;
+#[cfg(feature = "alloc")]
+use crate::charstar::CharStarBox;
+use crate::fd::OpenFlags;
+use crate::{CharStar, Errno, Fd, File, Result};
+
+/// The offset of the `d_reclen` field in a `linux_dirent64` structure.
+const RECLEN_OFFSET: usize = 16;
+/// The offset of the `d_type` field in a `linux_dirent64` structure.
+const TYPE_OFFSET: usize = 18;
+/// The offset of the `d_name` field in a `linux_dirent64` structure.
+const NAME_OFFSET: usize = 19;
+
+/// The buffer in which `getdents64` writes directory entries.
+#[repr(C, align(8))]
+struct DirBuffer([u8; 4096]);
+
+/// An open directory, which can be read entry by entry.
+///
+/// Entries are read lazily using the `getdents64` system call, a few of them at a time. This
+/// does not rely on `opendir` or `readdir`.
+///
+/// [`read`](Self::read) borrows each entry from the internal buffer of the [`Dir`], which
+/// avoids any allocation. With the `alloc` feature, [`Dir`] is also an [`Iterator`] over
+/// [`OwnedDirEntry`] values.
+///
+/// # Examples
+///
+/// ```ignore
+/// use ft::fs::Dir;
+///
+/// let mut dir = Dir::open(ft::charstar!(".")).unwrap();
+/// while let Some(entry) = dir.read().unwrap() {
+///     ft::printf!("{}\n", entry.name());
+/// }
+///
+/// for entry in Dir::open(ft::charstar!(".")).unwrap() {
+///     ft::printf!("{}\n", entry.unwrap().name());
+/// }
+/// ```
+pub struct Dir {
+    /// The file descriptor of the directory.
+    file: File,
+    /// The entries that were last read from the directory.
+    buf: DirBuffer,
+    /// The position of the next entry in `buf`.
+    pos: usize,
+    /// The number of bytes of `buf` that were filled by the last call to `getdents64`.
+    len: usize,
+}
+
+impl Dir {
+    /// Opens the directory at `path`.
+    #[inline]
+    #[doc(alias = "opendir")]
+    pub fn open(path: &CharStar) -> Result<Self> {
+        let fd = Fd::open(path, OpenFlags::READ_ONLY | OpenFlags::DIRECTORY)?;
+        Ok(Self::from_file(File::from_fd(fd)))
+    }
+
+    /// Creates a new [`Dir`] from a file that refers to a directory.
+    ///
+    /// Entries are read starting from the current offset of the file.
+    #[inline]
+    pub const fn from_file(file: File) -> Self {
+        Self {
+            file,
+            buf: DirBuffer([0; 4096]),
+            pos: 0,
+            len: 0,
+        }
+    }
+
+    /// Returns the file descriptor of the directory.
+    #[inline]
+    pub fn fd(&self) -> Fd {
+        *self.file
+    }
+
+    /// Refills the internal buffer with new entries.
+    ///
+    /// # Returns
+    ///
+    /// Whether the end of the directory has been reached.
+    fn refill(&mut self) -> Result<bool> {
+        let ret = unsafe {
+            libc::syscall(
+                libc::SYS_getdents64,
+                self.file.to_raw(),
+                self.buf.0.as_mut_ptr() as *mut c_void,
+                self.buf.0.len(),
+            )
+        };
+
+        if ret < 0 {
+            return Err(Errno::last());
+        }
+
+        self.pos = 0;
+        self.len = ret as usize;
+        Ok(ret == 0)
+    }
+
+    /// Reads the next entry of the directory.
+    ///
+    /// The `.` and `..` entries are skipped.
+    ///
+    /// # Returns
+    ///
+    /// The next entry, or `None` if the end of the directory has been reached.
+    #[doc(alias = "readdir")]
+    #[doc(alias = "getdents64")]
+    pub fn read(&mut self) -> Result<Option<DirEntry<'_>>> {
+        loop {
+            if self.pos >= self.len && self.refill()? {
+                return Ok(None);
+            }
+
+            let record = &self.buf.0[self.pos..self.len];
+            let reclen = u16::from_ne_bytes([record[RECLEN_OFFSET], record[RECLEN_OFFSET + 1]]);
+            let start = self.pos;
+            self.pos += reclen as usize;
+
+            let name = &self.buf.0[start + NAME_OFFSET..start + reclen as usize];
+            let name = unsafe { CharStar::from_ptr(name.as_ptr() as *const _) };
+            if name == "." || name == ".." {
+                continue;
+            }
+
+            let record = &self.buf.0[start..];
+            let inode = u64::from_ne_bytes(record[..8].try_into().unwrap());
+
+            return Ok(Some(DirEntry {
+                inode,
+                d_type: record[TYPE_OFFSET],
+                name,
+            }));
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Iterator for Dir {
+    type Item = Result<OwnedDirEntry>;
+
+    /// Reads the next entry of the directory, copying its name to the heap.
+    ///
+    /// See [`Dir::read`].
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.read() {
+            Ok(Some(entry)) => Some(entry.to_owned_entry()),
+            Ok(None) => None,
+            Err(err) => Some(Err(err)),
+        }
+    }
+}
+
+impl core::fmt::Debug for Dir {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Dir").field("fd", &self.fd()).finish()
+    }
+}
+
+/// An entry of a [`Dir`].
+#[derive(Debug, Clone, Copy)]
+#[doc(alias = "dirent")]
+pub struct DirEntry<'a> {
+    /// The inode number of the entry.
+    inode: u64,
+    /// The raw `d_type` value of the entry.
+    d_type: u8,
+    /// The name of the entry.
+    name: &'a CharStar,
+}
+
+impl<'a> DirEntry<'a> {
+    /// Returns the name of the entry.
+    ///
+    /// This is the name of the file within its directory, not a full path.
+    #[inline]
+    pub fn name(&self) -> &'a CharStar {
+        self.name
+    }
+
+    /// Returns the inode number of the entry.
+    #[inline]
+    pub fn inode(&self) -> u64 {
+        self.inode
+    }
+
+    /// Returns the raw `d_type` value of the entry, one of the `DT_*` constants.
+    #[inline]
+    pub fn d_type(&self) -> u8 {
+        self.d_type
+    }
+
+    /// Returns the type of the entry.
+    ///
+    /// Some file systems do not report the type of their entries. In that case,
+    /// [`FileType::Unknown`] is returned and [`metadata`](super::metadata) must be used instead.
+    #[inline]
+    pub fn file_type(&self) -> FileType {
+        file_type_from_raw(self.d_type)
+    }
+
+    /// Copies the name of the entry to the heap, so that it can outlive the [`Dir`].
+    #[cfg(feature = "alloc")]
+    pub fn to_owned_entry(&self) -> Result<OwnedDirEntry> {
+        Ok(OwnedDirEntry {
+            inode: self.inode,
+            d_type: self.d_type,
+            name: CharStarBox::from_bytes(self.name.as_bytes())?,
+        })
+    }
+}
+
+/// Converts a raw `d_type` value to a [`FileType`].
+fn file_type_from_raw(d_type: u8) -> FileType {
+    match d_type {
+        libc::DT_REG => FileType::Regular,
+        libc::DT_DIR => FileType::Directory,
+        libc::DT_LNK => FileType::Symlink,
+        libc::DT_CHR => FileType::CharDevice,
+        libc::DT_BLK => FileType::BlockDevice,
+        libc::DT_FIFO => FileType::Fifo,
+        libc::DT_SOCK => FileType::Socket,
+        _ => FileType::Unknown,
+    }
+}
+
+/// An entry of a [`Dir`] that owns its name, returned by the [`Iterator`] implementation of
+/// [`Dir`].
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+pub struct OwnedDirEntry {
+    /// The inode number of the entry.
+    inode: u64,
+    /// The raw `d_type` value of the entry.
+    d_type: u8,
+    /// The name of the entry.
+    name: CharStarBox,
+}
+
+#[cfg(feature = "alloc")]
+impl OwnedDirEntry {
+    /// Returns the name of the entry.
+    ///
+    /// This is the name of the file within its directory, not a full path.
+    #[inline]
+    pub fn name(&self) -> &CharStar {
+        &self.name
+    }
+
+    /// Returns the name of the entry, consuming it.
+    #[inline]
+    pub fn into_name(self) -> CharStarBox {
+        self.name
+    }
+
+    /// Returns the inode number of the entry.
+    #[inline]
+    pub fn inode(&self) -> u64 {
+        self.inode
+    }
+
+    /// Returns the raw `d_type` value of the entry, one of the `DT_*` constants.
+    #[inline]
+    pub fn d_type(&self) -> u8 {
+        self.d_type
+    }
+
+    /// Returns the type of the entry.
+    ///
+    /// See [`DirEntry::file_type`].
+    #[inline]
+    pub fn file_type(&self) -> FileType {
+        file_type_from_raw(self.d_type)
+    }
+}
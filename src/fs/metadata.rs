@@ -0,0 +1,225 @@
+//! Defines the [`Metadata`] type.
+
+use core::ffi::c_long;
+use core::time::Duration;
+
+use crate::fd::Mode;
+use crate::{CharStar, Errno, Fd, Gid, Instant, Result, Uid};
+
+/// The type of a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FileType {
+    /// A regular file.
+    Regular,
+    /// A directory.
+    Directory,
+    /// A symbolic link.
+    Symlink,
+    /// A character device.
+    CharDevice,
+    /// A block device.
+    BlockDevice,
+    /// A named pipe (FIFO).
+    Fifo,
+    /// A Unix domain socket.
+    Socket,
+    /// The type of the file is not known.
+    Unknown,
+}
+
+impl FileType {
+    /// Extracts the type of a file from the `st_mode` field of a `stat` structure.
+    pub const fn from_mode(mode: libc::mode_t) -> Self {
+        match mode & libc::S_IFMT {
+            libc::S_IFREG => Self::Regular,
+            libc::S_IFDIR => Self::Directory,
+            libc::S_IFLNK => Self::Symlink,
+            libc::S_IFCHR => Self::CharDevice,
+            libc::S_IFBLK => Self::BlockDevice,
+            libc::S_IFIFO => Self::Fifo,
+            libc::S_IFSOCK => Self::Socket,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// Information about a file, such as its type, size or permissions.
+#[derive(Clone, Copy)]
+#[doc(alias = "stat")]
+pub struct Metadata(libc::stat);
+
+impl Metadata {
+    /// Creates a new [`Metadata`] instance from the provided raw `stat` structure.
+    #[inline]
+    pub const fn from_raw(stat: libc::stat) -> Self {
+        Self(stat)
+    }
+
+    /// Returns the raw `stat` structure.
+    #[inline]
+    pub const fn as_raw(&self) -> &libc::stat {
+        &self.0
+    }
+
+    /// Returns the type of the file.
+    #[inline]
+    pub const fn file_type(&self) -> FileType {
+        FileType::from_mode(self.0.st_mode)
+    }
+
+    /// Returns whether the file is a directory.
+    #[inline]
+    pub const fn is_dir(&self) -> bool {
+        matches!(self.file_type(), FileType::Directory)
+    }
+
+    /// Returns whether the file is a regular file.
+    #[inline]
+    pub const fn is_file(&self) -> bool {
+        matches!(self.file_type(), FileType::Regular)
+    }
+
+    /// Returns whether the file is a symbolic link.
+    ///
+    /// This can only be true for metadata obtained through [`symlink_metadata`].
+    #[inline]
+    pub const fn is_symlink(&self) -> bool {
+        matches!(self.file_type(), FileType::Symlink)
+    }
+
+    /// Returns the size of the file, in bytes.
+    #[inline]
+    pub const fn size(&self) -> u64 {
+        self.0.st_size as u64
+    }
+
+    /// Returns the permissions of the file.
+    ///
+    /// The bits that describe the type of the file are not included.
+    #[inline]
+    pub const fn mode(&self) -> Mode {
+        Mode::from_bits_retain(self.0.st_mode & !libc::S_IFMT)
+    }
+
+    /// Returns the user that owns the file.
+    #[inline]
+    pub const fn uid(&self) -> Uid {
+        Uid::from_raw(self.0.st_uid)
+    }
+
+    /// Returns the group that owns the file.
+    #[inline]
+    pub const fn gid(&self) -> Gid {
+        Gid::from_raw(self.0.st_gid)
+    }
+
+    /// Returns the inode number of the file.
+    #[inline]
+    pub const fn inode(&self) -> libc::ino_t {
+        self.0.st_ino
+    }
+
+    /// Returns the identifier of the device that contains the file.
+    #[inline]
+    pub const fn device(&self) -> libc::dev_t {
+        self.0.st_dev
+    }
+
+    /// Returns the number of hard links to the file.
+    #[inline]
+    pub const fn links(&self) -> libc::nlink_t {
+        self.0.st_nlink
+    }
+
+    /// Returns the time at which the file was last accessed.
+    ///
+    /// The returned [`Instant`] is relative to the Unix epoch.
+    #[inline]
+    pub const fn accessed(&self) -> Instant {
+        make_instant(self.0.st_atime, self.0.st_atime_nsec)
+    }
+
+    /// Returns the time at which the contents of the file were last modified.
+    ///
+    /// The returned [`Instant`] is relative to the Unix epoch.
+    #[inline]
+    pub const fn modified(&self) -> Instant {
+        make_instant(self.0.st_mtime, self.0.st_mtime_nsec)
+    }
+
+    /// Returns the time at which the status of the file (its metadata) was last changed.
+    ///
+    /// The returned [`Instant`] is relative to the Unix epoch.
+    #[inline]
+    pub const fn changed(&self) -> Instant {
+        make_instant(self.0.st_ctime, self.0.st_ctime_nsec)
+    }
+}
+
+impl core::fmt::Debug for Metadata {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Metadata")
+            .field("file_type", &self.file_type())
+            .field("size", &self.size())
+            .field("mode", &self.mode())
+            .field("uid", &self.uid())
+            .field("gid", &self.gid())
+            .field("inode", &self.inode())
+            .field("modified", &self.modified())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Creates an [`Instant`] from the fields of a `stat` structure.
+///
+/// Timestamps before the Unix epoch are clamped to [`Instant::EPOCH`].
+const fn make_instant(secs: libc::time_t, nsecs: c_long) -> Instant {
+    if secs < 0 {
+        Instant::EPOCH
+    } else {
+        Instant(Duration::new(secs as u64, nsecs as u32))
+    }
+}
+
+impl Fd {
+    /// Returns the metadata of the file this file descriptor refers to.
+    #[inline]
+    #[doc(alias = "fstat")]
+    pub fn metadata(self) -> Result<Metadata> {
+        let mut stat = unsafe { core::mem::zeroed() };
+        let ret = unsafe { libc::fstat(self.to_raw(), &mut stat) };
+        if ret == 0 {
+            Ok(Metadata(stat))
+        } else {
+            Err(Errno::last())
+        }
+    }
+}
+
+/// Returns the metadata of the file at `path`, following symbolic links.
+#[inline]
+#[doc(alias = "stat")]
+pub fn metadata(path: &CharStar) -> Result<Metadata> {
+    let mut stat = unsafe { core::mem::zeroed() };
+    let ret = unsafe { libc::stat(path.as_ptr(), &mut stat) };
+    if ret == 0 {
+        Ok(Metadata(stat))
+    } else {
+        Err(Errno::last())
+    }
+}
+
+/// Returns the metadata of the file at `path`, without following symbolic links.
+///
+/// If `path` is a symbolic link, the metadata of the link itself is returned.
+#[inline]
+#[doc(alias = "lstat")]
+pub fn symlink_metadata(path: &CharStar) -> Result<Metadata> {
+    let mut stat = unsafe { core::mem::zeroed() };
+    let ret = unsafe { libc::lstat(path.as_ptr(), &mut stat) };
+    if ret == 0 {
+        Ok(Metadata(stat))
+    } else {
+        Err(Errno::last())
+    }
+}
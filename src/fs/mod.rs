@@ -0,0 +1,17 @@
+//! Functions to inspect and manipulate the file system.
+
+#[cfg(target_os = "linux")]
+mod dir;
+mod metadata;
+mod open_options;
+mod ops;
+#[cfg(feature = "alloc")]
+mod path;
+
+#[cfg(target_os = "linux")]
+pub use self::dir::*;
+pub use self::metadata::*;
+pub use self::open_options::*;
+pub use self::ops::*;
+#[cfg(feature = "alloc")]
+pub use self::path::*;
@@ -0,0 +1,181 @@
+//! Defines the [`OpenOptions`] builder.
+
+use crate::fd::{Mode, OpenFlags};
+use crate::{CharStar, Errno, Fd, File, Result};
+
+/// A builder used to open a [`File`] with specific options.
+///
+/// # Examples
+///
+/// ```ignore
+/// use ft::fs::OpenOptions;
+///
+/// // Fails with `Errno::EXIST` if another process holds the lock.
+/// let lock = OpenOptions::new()
+///     .write(true)
+///     .create_new(true)
+///     .open(ft::charstar!("/tmp/app.lock"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct OpenOptions {
+    read: bool,
+    write: bool,
+    append: bool,
+    create: bool,
+    create_new: bool,
+    truncate: bool,
+    mode: Mode,
+    custom_flags: OpenFlags,
+}
+
+impl OpenOptions {
+    /// Creates a new [`OpenOptions`] instance with every option disabled.
+    ///
+    /// Files created with these options get the mode `0o666`, before the file creation mask of
+    /// the process is applied.
+    pub fn new() -> Self {
+        Self {
+            read: false,
+            write: false,
+            append: false,
+            create: false,
+            create_new: false,
+            truncate: false,
+            mode: Mode::OWNER_READ
+                | Mode::OWNER_WRITE
+                | Mode::GROUP_READ
+                | Mode::GROUP_WRITE
+                | Mode::OTHER_READ
+                | Mode::OTHER_WRITE,
+            custom_flags: OpenFlags::empty(),
+        }
+    }
+
+    /// Sets whether the file should be readable.
+    #[inline]
+    pub fn read(&mut self, yes: bool) -> &mut Self {
+        self.read = yes;
+        self
+    }
+
+    /// Sets whether the file should be writable.
+    #[inline]
+    pub fn write(&mut self, yes: bool) -> &mut Self {
+        self.write = yes;
+        self
+    }
+
+    /// Sets whether writes should always append to the end of the file.
+    ///
+    /// This implies [`write`](Self::write).
+    #[inline]
+    pub fn append(&mut self, yes: bool) -> &mut Self {
+        self.append = yes;
+        self
+    }
+
+    /// Sets whether the file should be created if it does not exist.
+    #[inline]
+    pub fn create(&mut self, yes: bool) -> &mut Self {
+        self.create = yes;
+        self
+    }
+
+    /// Sets whether a new file must be created, failing with [`Errno::EXIST`] if it already
+    /// exists.
+    ///
+    /// The check is atomic, and symbolic links are not followed. When this option is set,
+    /// [`create`](Self::create) and [`truncate`](Self::truncate) are ignored.
+    #[inline]
+    #[doc(alias = "O_EXCL")]
+    pub fn create_new(&mut self, yes: bool) -> &mut Self {
+        self.create_new = yes;
+        self
+    }
+
+    /// Sets whether the file should be truncated to a size of zero when it is opened.
+    #[inline]
+    pub fn truncate(&mut self, yes: bool) -> &mut Self {
+        self.truncate = yes;
+        self
+    }
+
+    /// Sets the mode used when the file is created.
+    #[inline]
+    pub fn mode(&mut self, mode: Mode) -> &mut Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Sets additional flags to pass to `open`, such as [`OpenFlags::CLOEXEC`].
+    ///
+    /// The access mode and the flags controlled by the other options are ignored.
+    #[inline]
+    pub fn custom_flags(&mut self, flags: OpenFlags) -> &mut Self {
+        self.custom_flags = flags;
+        self
+    }
+
+    /// Computes the flags to pass to `open`.
+    ///
+    /// # Errors
+    ///
+    /// Creating or truncating a file requires write access, and this function fails with
+    /// [`Errno::INVAL`] if it is not requested.
+    fn flags(&self) -> Result<OpenFlags> {
+        let write = self.write || self.append;
+
+        let mut flags = match (self.read, write) {
+            (_, false) => OpenFlags::READ_ONLY,
+            (false, true) => OpenFlags::WRITE_ONLY,
+            (true, true) => OpenFlags::READ_WRITE,
+        };
+
+        if self.create_new {
+            flags |= OpenFlags::CREATE | OpenFlags::EXCL;
+        } else {
+            if self.create {
+                flags |= OpenFlags::CREATE;
+            }
+            if self.truncate {
+                flags |= OpenFlags::TRUNCATE;
+            }
+        }
+
+        if !write && flags.intersects(OpenFlags::CREATE | OpenFlags::TRUNCATE) {
+            return Err(Errno::INVAL);
+        }
+
+        if self.append {
+            flags |= OpenFlags::APPEND;
+        }
+
+        let controlled = OpenFlags::WRITE_ONLY
+            | OpenFlags::READ_WRITE
+            | OpenFlags::CREATE
+            | OpenFlags::EXCL
+            | OpenFlags::TRUNCATE
+            | OpenFlags::APPEND;
+        Ok(flags | self.custom_flags.difference(controlled))
+    }
+
+    /// Opens the file at `path` with these options.
+    #[inline]
+    pub fn open(&self, path: &CharStar) -> Result<File> {
+        self.open_at(Fd::CWD, path)
+    }
+
+    /// Opens the file at `path`, relative to the directory referred to by `dir`, with these
+    /// options.
+    #[doc(alias = "openat")]
+    pub fn open_at(&self, dir: Fd, path: &CharStar) -> Result<File> {
+        Fd::open_at(dir, path, self.flags()?, self.mode).map(File::from_fd)
+    }
+}
+
+impl Default for OpenOptions {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,246 @@
+//! Functions that create, remove or modify files.
+
+use core::ffi::{c_char, c_int};
+use core::mem::MaybeUninit;
+
+use bitflags::bitflags;
+
+use crate::charstar::CharStarBox;
+use crate::fd::Mode;
+use crate::{CharStar, Errno, Fd, Result};
+
+bitflags! {
+    /// The checks that can be performed by [`access`].
+    ///
+    /// When no flag is set, [`access`] only checks whether the file exists.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub struct AccessFlags: c_int {
+        /// Checks whether the file can be read.
+        const READ = libc::R_OK;
+        /// Checks whether the file can be written.
+        const WRITE = libc::W_OK;
+        /// Checks whether the file can be executed (or searched, for directories).
+        const EXECUTE = libc::X_OK;
+    }
+}
+
+/// Turns the return value of a function that returns `0` on success into a [`Result`].
+#[inline]
+fn check(ret: c_int) -> Result<()> {
+    if ret == 0 {
+        Ok(())
+    } else {
+        Err(Errno::last())
+    }
+}
+
+/// Calls `f` with buffers of increasing size until it manages to write a string into one of
+/// them.
+///
+/// `f` must return the length of the string it wrote, or `None` if the buffer was too small.
+/// The first `len` bytes of the buffer must have been initialized when `f` returns
+/// `Some(len)`.
+fn with_growing_buffer(
+    mut f: impl FnMut(&mut [MaybeUninit<u8>]) -> Result<Option<usize>>,
+) -> Result<CharStarBox> {
+    let mut capacity = 128;
+
+    loop {
+        let buf = crate::malloc::allocate(capacity)?;
+        let slice = unsafe {
+            core::slice::from_raw_parts_mut(buf.as_ptr() as *mut MaybeUninit<u8>, buf.len())
+        };
+
+        let ret = match f(slice) {
+            Ok(Some(len)) => {
+                debug_assert!(len <= slice.len());
+                let init = unsafe { core::slice::from_raw_parts(slice.as_ptr().cast::<u8>(), len) };
+                Some(CharStarBox::from_bytes(init).map_err(Errno::from))
+            }
+            Ok(None) => None,
+            Err(err) => Some(Err(err)),
+        };
+
+        capacity = slice.len().saturating_mul(2);
+        unsafe { crate::malloc::deallocate(buf.as_non_null_ptr()) };
+
+        if let Some(ret) = ret {
+            return ret;
+        }
+    }
+}
+
+/// Removes the file at `path`.
+///
+/// This cannot remove directories, use [`rmdir`] for that.
+#[inline]
+pub fn unlink(path: &CharStar) -> Result<()> {
+    check(unsafe { libc::unlink(path.as_ptr()) })
+}
+
+/// Like [`unlink`], but relative paths are resolved from the directory `dir` instead of the
+/// current working directory.
+#[inline]
+#[doc(alias = "unlinkat")]
+pub fn unlink_at(dir: Fd, path: &CharStar) -> Result<()> {
+    check(unsafe { libc::unlinkat(dir.to_raw(), path.as_ptr(), 0) })
+}
+
+/// Renames the file at `from` to `to`, replacing `to` if it already exists.
+#[inline]
+pub fn rename(from: &CharStar, to: &CharStar) -> Result<()> {
+    check(unsafe { libc::rename(from.as_ptr(), to.as_ptr()) })
+}
+
+/// Like [`rename`], but relative paths are resolved from the directories `from_dir` and
+/// `to_dir` respectively.
+#[inline]
+#[doc(alias = "renameat")]
+pub fn rename_at(from_dir: Fd, from: &CharStar, to_dir: Fd, to: &CharStar) -> Result<()> {
+    check(unsafe {
+        libc::renameat(
+            from_dir.to_raw(),
+            from.as_ptr(),
+            to_dir.to_raw(),
+            to.as_ptr(),
+        )
+    })
+}
+
+/// Creates a new directory at `path` with the provided permissions.
+///
+/// The permissions are masked by the file creation mask of the process (see
+/// [`Mode::make_current`]).
+#[inline]
+pub fn mkdir(path: &CharStar, mode: Mode) -> Result<()> {
+    check(unsafe { libc::mkdir(path.as_ptr(), mode.bits()) })
+}
+
+/// Like [`mkdir`], but relative paths are resolved from the directory `dir`.
+#[inline]
+#[doc(alias = "mkdirat")]
+pub fn mkdir_at(dir: Fd, path: &CharStar, mode: Mode) -> Result<()> {
+    check(unsafe { libc::mkdirat(dir.to_raw(), path.as_ptr(), mode.bits()) })
+}
+
+/// Removes the directory at `path`, which must be empty.
+#[inline]
+pub fn rmdir(path: &CharStar) -> Result<()> {
+    check(unsafe { libc::rmdir(path.as_ptr()) })
+}
+
+/// Like [`rmdir`], but relative paths are resolved from the directory `dir`.
+#[inline]
+#[doc(alias = "unlinkat")]
+pub fn rmdir_at(dir: Fd, path: &CharStar) -> Result<()> {
+    check(unsafe { libc::unlinkat(dir.to_raw(), path.as_ptr(), libc::AT_REMOVEDIR) })
+}
+
+/// Creates a symbolic link at `link` that points to `target`.
+///
+/// `target` is not required to exist.
+#[inline]
+pub fn symlink(target: &CharStar, link: &CharStar) -> Result<()> {
+    check(unsafe { libc::symlink(target.as_ptr(), link.as_ptr()) })
+}
+
+/// Like [`symlink`], but a relative `link` is resolved from the directory `dir`.
+#[inline]
+#[doc(alias = "symlinkat")]
+pub fn symlink_at(target: &CharStar, dir: Fd, link: &CharStar) -> Result<()> {
+    check(unsafe { libc::symlinkat(target.as_ptr(), dir.to_raw(), link.as_ptr()) })
+}
+
+/// Returns the target of the symbolic link at `path`.
+#[inline]
+pub fn readlink(path: &CharStar) -> Result<CharStarBox> {
+    readlink_at(Fd::CWD, path)
+}
+
+/// Like [`readlink`], but relative paths are resolved from the directory `dir`.
+#[doc(alias = "readlinkat")]
+pub fn readlink_at(dir: Fd, path: &CharStar) -> Result<CharStarBox> {
+    with_growing_buffer(|buf| {
+        let ret = unsafe {
+            libc::readlinkat(
+                dir.to_raw(),
+                path.as_ptr(),
+                buf.as_mut_ptr() as *mut c_char,
+                buf.len(),
+            )
+        };
+
+        if ret < 0 {
+            Err(Errno::last())
+        } else if ret as usize >= buf.len() {
+            // The target might have been truncated.
+            Ok(None)
+        } else {
+            Ok(Some(ret as usize))
+        }
+    })
+}
+
+/// Changes the permissions of the file at `path`.
+#[inline]
+pub fn chmod(path: &CharStar, mode: Mode) -> Result<()> {
+    check(unsafe { libc::chmod(path.as_ptr(), mode.bits()) })
+}
+
+/// Like [`chmod`], but relative paths are resolved from the directory `dir`.
+#[inline]
+#[doc(alias = "fchmodat")]
+pub fn chmod_at(dir: Fd, path: &CharStar, mode: Mode) -> Result<()> {
+    check(unsafe { libc::fchmodat(dir.to_raw(), path.as_ptr(), mode.bits(), 0) })
+}
+
+/// Checks whether the calling process can access the file at `path`.
+///
+/// The check is done using the real user and group IDs of the process.
+///
+/// # Errors
+///
+/// This function fails with [`Errno::ACCES`] if the access would be denied, and with
+/// [`Errno::NOENT`] if the file does not exist.
+#[inline]
+pub fn access(path: &CharStar, flags: AccessFlags) -> Result<()> {
+    check(unsafe { libc::access(path.as_ptr(), flags.bits()) })
+}
+
+/// Like [`access`], but relative paths are resolved from the directory `dir`.
+#[inline]
+#[doc(alias = "faccessat")]
+pub fn access_at(dir: Fd, path: &CharStar, flags: AccessFlags) -> Result<()> {
+    check(unsafe { libc::faccessat(dir.to_raw(), path.as_ptr(), flags.bits(), 0) })
+}
+
+/// Changes the current working directory of the process.
+#[inline]
+pub fn chdir(path: &CharStar) -> Result<()> {
+    check(unsafe { libc::chdir(path.as_ptr()) })
+}
+
+/// Returns the current working directory of the process.
+pub fn getcwd() -> Result<CharStarBox> {
+    with_growing_buffer(|buf| {
+        let ret = unsafe { libc::getcwd(buf.as_mut_ptr() as *mut c_char, buf.len()) };
+
+        if !ret.is_null() {
+            Ok(Some(unsafe { CharStar::from_ptr(ret).len() }))
+        } else {
+            match Errno::last() {
+                Errno::RANGE => Ok(None),
+                err => Err(err),
+            }
+        }
+    })
+}
+
+impl Fd {
+    /// Makes the directory this file descriptor refers to the current working directory.
+    #[inline]
+    #[doc(alias = "fchdir")]
+    pub fn chdir(self) -> Result<()> {
+        check(unsafe { libc::fchdir(self.to_raw()) })
+    }
+}
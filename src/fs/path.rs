@@ -0,0 +1,344 @@
+//! Defines the [`PathBuf`] type.
+
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Deref;
+
+use alloc::vec::Vec;
+
+use crate::charstar::CharStarBox;
+use crate::malloc::OutOfMemory;
+use crate::{CharStar, Errno, Result};
+
+/// A component of a path, as returned by [`Components`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Component<'a> {
+    /// The leading `/` of an absolute path.
+    RootDir,
+    /// A `.` component.
+    CurDir,
+    /// A `..` component.
+    ParentDir,
+    /// Any other component, such as `foo` in `/foo/bar`.
+    Normal(&'a [u8]),
+}
+
+impl<'a> Component<'a> {
+    /// Returns the bytes of this component.
+    #[inline]
+    pub fn as_bytes(&self) -> &'a [u8] {
+        match self {
+            Self::RootDir => b"/",
+            Self::CurDir => b".",
+            Self::ParentDir => b"..",
+            Self::Normal(name) => name,
+        }
+    }
+}
+
+/// An iterator over the [`Component`]s of a path.
+///
+/// Repeated separators are ignored, as well as `.` components that are not at the start of the
+/// path.
+#[derive(Debug, Clone)]
+pub struct Components<'a> {
+    /// The part of the path that remains to be parsed.
+    rest: &'a [u8],
+    /// Whether the iterator is at the start of the path.
+    at_start: bool,
+}
+
+impl<'a> Components<'a> {
+    /// Creates a new [`Components`] iterator over the provided path.
+    #[inline]
+    pub fn new(path: &'a [u8]) -> Self {
+        Self {
+            rest: path,
+            at_start: true,
+        }
+    }
+}
+
+impl<'a> Iterator for Components<'a> {
+    type Item = Component<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if core::mem::take(&mut self.at_start) {
+            if let Some(rest) = self.rest.strip_prefix(b"/") {
+                self.rest = rest;
+                return Some(Component::RootDir);
+            }
+
+            if self.rest == b"." || self.rest.starts_with(b"./") {
+                self.rest = &self.rest[1..];
+                return Some(Component::CurDir);
+            }
+        }
+
+        loop {
+            let start = self.rest.iter().position(|&b| b != b'/')?;
+            let rest = &self.rest[start..];
+            let end = rest.iter().position(|&b| b == b'/').unwrap_or(rest.len());
+            let (name, rest) = rest.split_at(end);
+            self.rest = rest;
+
+            match name {
+                b"." => continue,
+                b".." => return Some(Component::ParentDir),
+                _ => return Some(Component::Normal(name)),
+            }
+        }
+    }
+}
+
+impl FusedIterator for Components<'_> {}
+
+/// Removes the trailing separators of `path`, unless it is only made of separators.
+fn trim_trailing_separators(path: &[u8]) -> &[u8] {
+    match path.iter().rposition(|&b| b != b'/') {
+        Some(last) => &path[..=last],
+        None => &path[..path.len().min(1)],
+    }
+}
+
+/// Normalizes `path` lexically and appends the result to `out`.
+///
+/// `.` components are removed and `..` components remove the preceding component. Nothing in
+/// `out` can be removed.
+///
+/// # Errors
+///
+/// This function fails with [`Errno::ACCES`] if a `..` component would remove a component that
+/// does not belong to `path`.
+fn normalize_into(out: &mut Vec<u8>, path: &[u8]) -> Result<()> {
+    let floor = out.len();
+
+    for component in Components::new(path) {
+        match component {
+            Component::RootDir | Component::CurDir => (),
+            Component::ParentDir => {
+                if out.len() == floor {
+                    return Err(Errno::ACCES);
+                }
+
+                let last = out[floor..]
+                    .iter()
+                    .rposition(|&b| b == b'/')
+                    .map_or(floor, |index| floor + index);
+                out.truncate(last);
+            }
+            Component::Normal(name) => {
+                out.try_reserve(name.len() + 1).map_err(|_| Errno::NOMEM)?;
+                if out.len() != floor || floor != 0 && out[floor - 1] != b'/' {
+                    out.push(b'/');
+                }
+                out.extend_from_slice(name);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// An owned, mutable path.
+///
+/// This is a [`CharStarBox`] with methods to manipulate its components. The path is always
+/// null-terminated, and can be passed directly to the functions of [`ft::fs`](crate::fs).
+#[derive(Clone)]
+pub struct PathBuf(CharStarBox);
+
+impl PathBuf {
+    /// Creates a new [`PathBuf`] by copying the provided bytes.
+    #[inline]
+    pub fn from_bytes(path: &[u8]) -> core::result::Result<Self, OutOfMemory> {
+        CharStarBox::from_bytes(path).map(Self)
+    }
+
+    /// Returns the underlying [`CharStarBox`].
+    #[inline]
+    pub fn into_charstar_box(self) -> CharStarBox {
+        self.0
+    }
+
+    /// Returns whether the path is absolute (starts with a `/`).
+    #[inline]
+    pub fn is_absolute(&self) -> bool {
+        self.as_bytes().first() == Some(&b'/')
+    }
+
+    /// Returns an iterator over the components of the path.
+    #[inline]
+    pub fn components(&self) -> Components<'_> {
+        Components::new(self.as_bytes())
+    }
+
+    /// Returns the path without its last component.
+    ///
+    /// If the path has a single relative component, the empty path is returned.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the path is empty or only contains a root.
+    pub fn parent(&self) -> Option<&[u8]> {
+        let path = trim_trailing_separators(self.as_bytes());
+        if path.is_empty() || path == b"/" {
+            return None;
+        }
+
+        match path.iter().rposition(|&b| b == b'/') {
+            Some(index) => Some(match trim_trailing_separators(&path[..index]) {
+                b"" => b"/",
+                parent => parent,
+            }),
+            None => Some(b""),
+        }
+    }
+
+    /// Returns the last component of the path.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the path is empty, only contains a root, is only `.`, or ends with `..`. Other
+    /// `.` components are ignored, so the file name of `a/.` is `a`.
+    pub fn file_name(&self) -> Option<&[u8]> {
+        match self.components().last()? {
+            Component::Normal(name) => Some(name),
+            _ => None,
+        }
+    }
+
+    /// Returns the extension of the last component of the path, without the leading `.`.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the file name has no extension. A name that starts with a `.` and contains no
+    /// other `.`, such as `.bashrc`, has no extension.
+    pub fn extension(&self) -> Option<&[u8]> {
+        let name = self.file_name()?;
+        match name.iter().rposition(|&b| b == b'.')? {
+            0 => None,
+            index => Some(&name[index + 1..]),
+        }
+    }
+
+    /// Appends `path` to this path, inserting a separator if needed.
+    ///
+    /// If `path` is absolute, it replaces the current path entirely. Use
+    /// [`push_within`](Self::push_within) when `path` comes from an untrusted source.
+    pub fn push(&mut self, path: &[u8]) -> core::result::Result<(), OutOfMemory> {
+        let this = self.as_bytes();
+
+        self.0 = if path.first() == Some(&b'/') || this.is_empty() {
+            CharStarBox::from_bytes(path)?
+        } else if this.last() == Some(&b'/') {
+            CharStarBox::concat(&[this, path])?
+        } else {
+            CharStarBox::concat(&[this, b"/", path])?
+        };
+
+        Ok(())
+    }
+
+    /// Removes the last component of the path.
+    ///
+    /// # Returns
+    ///
+    /// Whether the path had a parent. When it did not, the path is left unchanged.
+    pub fn pop(&mut self) -> core::result::Result<bool, OutOfMemory> {
+        match self.parent() {
+            Some(parent) => {
+                self.0 = CharStarBox::from_bytes(parent)?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Returns a lexically normalized copy of this path.
+    ///
+    /// Repeated separators and `.` components are removed, and `..` components remove the
+    /// component that precedes them. The file system is not accessed, meaning that symbolic
+    /// links are not resolved.
+    ///
+    /// An empty relative result is normalized to `.`.
+    ///
+    /// # Errors
+    ///
+    /// This function fails with [`Errno::ACCES`] if a `..` component would escape the start of
+    /// the path (or the root directory, for absolute paths), and with [`Errno::NOMEM`] if the
+    /// system is out of memory.
+    pub fn normalize(&self) -> Result<Self> {
+        let mut out = Vec::new();
+        out.try_reserve(self.as_bytes().len() + 1)
+            .map_err(|_| Errno::NOMEM)?;
+
+        if self.is_absolute() {
+            out.push(b'/');
+        }
+        normalize_into(&mut out, self.as_bytes())?;
+        if out.is_empty() {
+            out.push(b'.');
+        }
+
+        Ok(Self::from_bytes(&out)?)
+    }
+
+    /// Appends the lexically normalized form of `path` to this path, making sure that the
+    /// result cannot refer to a file outside of it.
+    ///
+    /// `path` is always treated as relative, even if it starts with a `/`. This is the function
+    /// to use when joining a document root and a path that comes from a request.
+    ///
+    /// # Errors
+    ///
+    /// This function fails with [`Errno::ACCES`] if a `..` component of `path` would escape this
+    /// path, and with [`Errno::NOMEM`] if the system is out of memory. In both cases, this path
+    /// is left unchanged.
+    pub fn push_within(&mut self, path: &[u8]) -> Result<()> {
+        let this = self.as_bytes();
+
+        let mut out = Vec::new();
+        out.try_reserve(this.len() + path.len() + 2)
+            .map_err(|_| Errno::NOMEM)?;
+        out.extend_from_slice(this);
+        normalize_into(&mut out, path)?;
+
+        self.0 = CharStarBox::from_bytes(&out)?;
+        Ok(())
+    }
+}
+
+impl From<CharStarBox> for PathBuf {
+    #[inline]
+    fn from(path: CharStarBox) -> Self {
+        Self(path)
+    }
+}
+
+impl Deref for PathBuf {
+    type Target = CharStar;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl AsRef<CharStar> for PathBuf {
+    #[inline]
+    fn as_ref(&self) -> &CharStar {
+        &self.0
+    }
+}
+
+impl fmt::Debug for PathBuf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl fmt::Display for PathBuf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
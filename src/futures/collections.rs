@@ -2,21 +2,22 @@ use core::future::Future;
 use core::pin::Pin;
 use core::task::{ready, Context, Poll};
 
-use crate::collections::ReadBuffer;
-use crate::{Fd, MemchrExt, Result};
+use crate::collections::{ReadBuffer, WriteBuffer};
+use crate::io::AsyncRead;
+use crate::{Errno, Fd, MemchrExt, Result};
 
 /// A [`Future`] that completes when some data can be written to a [`ReadBuffer`].
 #[doc(alias = "fill_with_fd")]
-pub struct FillWithFd<'a> {
-    /// The file descriptor that will be read into the buffer.
+pub struct FillWithFd<'a, R = Fd> {
+    /// The reader (usually a file descriptor) that will be read into the buffer.
     ///
-    /// It is expected to be in non-blocking mode.
-    pub fd: Fd,
+    /// File descriptors are expected to be in non-blocking mode.
+    pub fd: R,
     /// The buffer that will be filled with data.
     pub buf: &'a mut ReadBuffer,
 }
 
-impl<'a> Future for FillWithFd<'a> {
+impl<R: AsyncRead + Unpin> Future for FillWithFd<'_, R> {
     type Output = Result<usize>;
 
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
@@ -35,17 +36,17 @@ impl<'a> Future for FillWithFd<'a> {
 /// A future that reads a file descriptor until a delimiter is found.
 ///
 /// See [`ReadBuffer::read_until`] for more information.
-pub struct ReadUntil<'a, 'd> {
-    fd: Fd,
+pub struct ReadUntil<'a, 'd, R = Fd> {
+    fd: R,
     buf: Option<&'a mut ReadBuffer>,
     delimiter: &'d [u8],
     batch_size: usize,
     checked: usize,
 }
 
-impl<'a, 'd> ReadUntil<'a, 'd> {
+impl<'a, 'd, R> ReadUntil<'a, 'd, R> {
     /// Creates a new `ReadUntil` future.
-    pub fn new(fd: Fd, buf: &'a mut ReadBuffer, delimiter: &'d [u8]) -> Self {
+    pub fn new(fd: R, buf: &'a mut ReadBuffer, delimiter: &'d [u8]) -> Self {
         Self {
             fd,
             buf: Some(buf),
@@ -56,7 +57,7 @@ impl<'a, 'd> ReadUntil<'a, 'd> {
     }
 }
 
-impl<'a, 'd> Future for ReadUntil<'a, 'd> {
+impl<'a, R: AsyncRead + Unpin> Future for ReadUntil<'a, '_, R> {
     type Output = Result<&'a mut [u8]>;
 
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
@@ -96,6 +97,12 @@ impl<'a, 'd> Future for ReadUntil<'a, 'd> {
 
             buf.reserve(this.batch_size)?;
             let count = ready!(this.fd.poll_read(buf.spare_capacity_mut(), cx))?;
+            if count == 0 {
+                let len = buf.pending().len();
+                return Poll::Ready(Ok(unsafe {
+                    this.buf.take().unwrap_unchecked().consume_unchecked(len)
+                }));
+            }
             unsafe { buf.assume_init(count) };
 
             this.batch_size = this.batch_size.saturating_mul(2);
@@ -103,18 +110,18 @@ impl<'a, 'd> Future for ReadUntil<'a, 'd> {
     }
 }
 
-/// A future that reads a file descriptor until a delimiter is found.
+/// A future that reads a fixed number of bytes from a file descriptor.
 ///
-/// See [`ReadBuffer::read_until`] for more information.
-pub struct ReadExact<'a> {
-    fd: Fd,
+/// See [`ReadBuffer::read_exact`] for more information.
+pub struct ReadExact<'a, R = Fd> {
+    fd: R,
     buf: Option<&'a mut ReadBuffer>,
     count: usize,
 }
 
-impl<'a> ReadExact<'a> {
+impl<'a, R> ReadExact<'a, R> {
     /// Creates a new `ReadExact` future.
-    pub fn new(fd: Fd, buf: &'a mut ReadBuffer, count: usize) -> Self {
+    pub fn new(fd: R, buf: &'a mut ReadBuffer, count: usize) -> Self {
         Self {
             fd,
             buf: Some(buf),
@@ -123,7 +130,7 @@ impl<'a> ReadExact<'a> {
     }
 }
 
-impl<'a> Future for ReadExact<'a> {
+impl<'a, R: AsyncRead + Unpin> Future for ReadExact<'a, R> {
     type Output = Result<&'a mut [u8]>;
 
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
@@ -133,7 +140,7 @@ impl<'a> Future for ReadExact<'a> {
         let buf = unsafe { this.buf.as_mut().unwrap_unchecked() };
 
         loop {
-            if buf.pending().len() > this.count {
+            if buf.pending().len() >= this.count {
                 return Poll::Ready(Ok(unsafe {
                     this.buf
                         .take()
@@ -144,7 +151,32 @@ impl<'a> Future for ReadExact<'a> {
 
             buf.reserve(this.count.saturating_sub(buf.pending().len()))?;
             let count = ready!(this.fd.poll_read(buf.spare_capacity_mut(), cx))?;
+            if count == 0 {
+                return Poll::Ready(Err(Errno::NODATA));
+            }
             unsafe { buf.assume_init(count) };
         }
     }
 }
+
+/// A [`Future`] that completes once all the pending data of a [`WriteBuffer`] has been written
+/// to its file descriptor.
+///
+/// See [`WriteBuffer::flush`] for more information.
+#[derive(Debug)]
+#[doc(alias = "flush")]
+pub struct Flush<'a> {
+    /// The buffer to flush.
+    ///
+    /// Its file descriptor is expected to be in non-blocking mode.
+    pub buf: &'a mut WriteBuffer,
+}
+
+impl Future for Flush<'_> {
+    type Output = Result<()>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.get_mut().buf.poll_flush(cx)
+    }
+}
@@ -0,0 +1,66 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use crate::fd::{EventFd, SigInfo, SignalFd, TimerFd};
+use crate::Result;
+
+/// A future that completes once the counter of an [`EventFd`] is non-zero, and resets it.
+///
+/// See [`EventFd::consume`] for more information.
+#[derive(Debug, Clone)]
+pub struct Consume<'a> {
+    /// The event file descriptor.
+    ///
+    /// It is expected to be in non-blocking mode.
+    pub event: &'a EventFd,
+}
+
+impl Future for Consume<'_> {
+    type Output = Result<u64>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.event.poll_consume(cx)
+    }
+}
+
+/// A future that completes once a [`TimerFd`] has expired.
+///
+/// See [`TimerFd::wait`] for more information.
+#[derive(Debug, Clone)]
+pub struct Expirations<'a> {
+    /// The timer.
+    ///
+    /// It is expected to be in non-blocking mode.
+    pub timer: &'a TimerFd,
+}
+
+impl Future for Expirations<'_> {
+    type Output = Result<u64>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.timer.poll_wait(cx)
+    }
+}
+
+/// A future that completes once a signal has been received through a [`SignalFd`].
+///
+/// See [`SignalFd::read`] for more information.
+#[derive(Debug, Clone)]
+pub struct NextSignal<'a> {
+    /// The signal file descriptor.
+    ///
+    /// It is expected to be in non-blocking mode.
+    pub signals: &'a SignalFd,
+}
+
+impl Future for NextSignal<'_> {
+    type Output = Result<SigInfo>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.signals.poll_read(cx)
+    }
+}
@@ -3,7 +3,8 @@ use core::mem::MaybeUninit;
 use core::pin::Pin;
 use core::task::{ready, Context, Poll};
 
-use crate::fd::{PollFd, PollFlags};
+use crate::fd::{IoSlice, IoSliceMut};
+use crate::io::{AsyncRead, AsyncWrite};
 use crate::{Errno, Fd, Result};
 
 #[cfg(feature = "alloc")]
@@ -16,47 +17,122 @@ use alloc::vec::Vec;
 /// This future assumes that the file descriptor is in non-blocking mode.
 #[derive(Debug, Clone)]
 #[doc(alias = "write")]
-pub struct Write<'a> {
-    /// The file descriptor that will be written.
+pub struct Write<'a, W = Fd> {
+    /// The writer (usually a file descriptor) that will be written.
     ///
-    /// It is expected to be in non-blocking mode.
-    pub fd: Fd,
+    /// File descriptors are expected to be in non-blocking mode.
+    pub fd: W,
     /// The data to write.
     pub data: &'a [u8],
 }
 
-impl<'a> Future for Write<'a> {
+impl<W: AsyncWrite + Unpin> Future for Write<'_, W> {
     type Output = Result<usize>;
 
     #[inline]
     fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
-        self.fd.poll_write(self.data, cx)
+        let this = self.get_mut();
+        this.fd.poll_write(this.data, cx)
     }
 }
 
 /// A future that completes once all the data it references has been written to a file descriptor.
 ///
+/// If the writer stops accepting data before everything has been written, the future fails
+/// with [`Errno::NOSPC`].
+///
 /// # Notes
 ///
 /// This future assumes that the file descriptor is in non-blocking mode.
 #[doc(alias = "write")]
 #[derive(Debug, Clone)]
-pub struct WriteAll<'a> {
+pub struct WriteAll<'a, W = Fd> {
+    /// The writer (usually a file descriptor) that will be written.
+    ///
+    /// File descriptors are expected to be in non-blocking mode.
+    pub fd: W,
+    /// The data that must still be written.
+    pub data: &'a [u8],
+}
+
+impl<W: AsyncWrite + Unpin> Future for WriteAll<'_, W> {
+    type Output = Result<()>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        while !this.data.is_empty() {
+            match ready!(this.fd.poll_write(this.data, cx)) {
+                Ok(0) => return Poll::Ready(Err(Errno::NOSPC)),
+                Ok(count) => this.data = unsafe { this.data.get_unchecked(count..) },
+                Err(err) => return Poll::Ready(Err(err)),
+            }
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// A future that completes when some data can be written to a file descriptor from a list of
+/// buffers.
+///
+/// # Notes
+///
+/// This future assumes that the file descriptor is in non-blocking mode.
+#[derive(Debug, Clone)]
+#[doc(alias = "writev")]
+pub struct WriteVectored<'a, 'b> {
+    /// The file descriptor that will be written.
+    ///
+    /// It is expected to be in non-blocking mode.
+    pub fd: Fd,
+    /// The buffers to write.
+    pub bufs: &'a [IoSlice<'b>],
+}
+
+impl Future for WriteVectored<'_, '_> {
+    type Output = Result<usize>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        self.fd.poll_write_vectored(self.bufs, cx)
+    }
+}
+
+/// A future that completes once all the data of a list of buffers has been written to a file
+/// descriptor.
+///
+/// If the file descriptor stops accepting data before everything has been written, the future
+/// fails with [`Errno::NOSPC`].
+///
+/// # Notes
+///
+/// This future assumes that the file descriptor is in non-blocking mode.
+#[doc(alias = "writev")]
+#[derive(Debug)]
+pub struct WriteAllVectored<'a, 'b> {
     /// The file descriptor that will be written.
     ///
     /// This file descriptor is expected to be in non-blocking mode.
     pub fd: Fd,
-    /// The data that must still be written.
-    pub data: &'a [u8],
+    /// The buffers that must still be written.
+    ///
+    /// They are advanced as data is written.
+    pub bufs: &'a mut [IoSlice<'b>],
 }
 
-impl<'a> Future for WriteAll<'a> {
+impl Future for WriteAllVectored<'_, '_> {
     type Output = Result<()>;
 
-    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-        while !self.data.is_empty() {
-            match ready!(self.fd.poll_write(self.data, cx)) {
-                Ok(count) => self.data = unsafe { self.data.get_unchecked(count..) },
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        IoSlice::advance_slices(&mut this.bufs, 0);
+
+        while !this.bufs.is_empty() {
+            match ready!(this.fd.poll_write_vectored(this.bufs, cx)) {
+                // Nothing can be written anymore, which would make the loop spin forever.
+                Ok(0) => return Poll::Ready(Err(Errno::NOSPC)),
+                Ok(count) => IoSlice::advance_slices(&mut this.bufs, count),
                 Err(err) => return Poll::Ready(Err(err)),
             }
         }
@@ -72,21 +148,48 @@ impl<'a> Future for WriteAll<'a> {
 /// This future assumes that the file descriptor is in non-blocking mode.
 #[doc(alias = "read")]
 #[derive(Debug)]
-pub struct Read<'a> {
+pub struct Read<'a, R = Fd> {
+    /// The reader (usually a file descriptor) that will be read.
+    ///
+    /// File descriptors are expected to be in non-blocking mode.
+    pub fd: R,
+    /// The buffer to read the data into.
+    pub buf: &'a mut [MaybeUninit<u8>],
+}
+
+impl<R: AsyncRead + Unpin> Future for Read<'_, R> {
+    type Output = Result<usize>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        this.fd.poll_read(this.buf, cx)
+    }
+}
+
+/// Reads data from a file descriptor into a list of buffers.
+///
+/// # Notes
+///
+/// This future assumes that the file descriptor is in non-blocking mode.
+#[doc(alias = "readv")]
+#[derive(Debug)]
+pub struct ReadVectored<'a, 'b> {
     /// The file descriptor that will be read.
     ///
     /// It is expected to be in non-blocking mode.
     pub fd: Fd,
-    /// The buffer to read the data into.
-    pub buf: &'a mut [MaybeUninit<u8>],
+    /// The buffers to read the data into.
+    pub bufs: &'a mut [IoSliceMut<'b>],
 }
 
-impl<'a> Future for Read<'a> {
+impl Future for ReadVectored<'_, '_> {
     type Output = Result<usize>;
 
     #[inline]
-    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-        self.fd.poll_read(self.buf, cx)
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        this.fd.poll_read_vectored(this.bufs, cx)
     }
 }
 
@@ -97,15 +200,19 @@ impl<'a> Future for Read<'a> {
 /// This future assumes that the file descriptor is in non-blocking mode.
 #[doc(alias = "read")]
 #[derive(Debug, Clone)]
-pub struct ReadOne(pub Fd);
+pub struct ReadOne<R = Fd>(pub R);
 
-impl Future for ReadOne {
+impl<R: AsyncRead + Unpin> Future for ReadOne<R> {
     type Output = Result<Option<u8>>;
 
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         let mut buf = MaybeUninit::uninit();
 
-        match ready!(self.0.poll_read(core::slice::from_mut(&mut buf), cx)) {
+        match ready!(self
+            .get_mut()
+            .0
+            .poll_read(core::slice::from_mut(&mut buf), cx))
+        {
             Ok(0) => Poll::Ready(Ok(None)),
             Ok(_) => Poll::Ready(Ok(Some(unsafe { buf.assume_init() }))),
             Err(err) => Poll::Ready(Err(err)),
@@ -122,24 +229,26 @@ impl Future for ReadOne {
 #[cfg(feature = "alloc")]
 #[doc(alias = "read")]
 #[derive(Debug)]
-pub struct ReadOnceToVec<'a> {
-    /// The file descriptor that will be read.
+pub struct ReadOnceToVec<'a, R = Fd> {
+    /// The reader (usually a file descriptor) that will be read.
     ///
-    /// It is expected to be in non-blocking mode.
-    pub fd: Fd,
+    /// File descriptors are expected to be in non-blocking mode.
+    pub fd: R,
     /// The buffer to read the data into.
     pub buf: &'a mut Vec<u8>,
 }
 
 #[cfg(feature = "alloc")]
-impl<'a> Future for ReadOnceToVec<'a> {
+impl<R: AsyncRead + Unpin> Future for ReadOnceToVec<'_, R> {
     type Output = Result<usize>;
 
-    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-        match ready!(self.fd.poll_read(self.buf.spare_capacity_mut(), cx)) {
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        match ready!(this.fd.poll_read(this.buf.spare_capacity_mut(), cx)) {
             Ok(count) => {
-                let len = self.buf.len();
-                unsafe { self.buf.set_len(len + count) };
+                let len = this.buf.len();
+                unsafe { this.buf.set_len(len + count) };
                 Poll::Ready(Ok(count))
             }
             Err(err) => Poll::Ready(Err(err)),
@@ -156,15 +265,16 @@ impl<'a> Future for ReadOnceToVec<'a> {
 #[cfg(feature = "alloc")]
 #[doc(alias = "read")]
 #[derive(Debug)]
-pub struct ReadToVec<'a> {
-    fd: Fd,
+pub struct ReadToVec<'a, R = Fd> {
+    fd: R,
     buf: &'a mut Vec<u8>,
     batch_size: usize,
 }
 
-impl<'a> ReadToVec<'a> {
+#[cfg(feature = "alloc")]
+impl<'a, R> ReadToVec<'a, R> {
     /// Creates a new `ReadToVec` future.
-    pub fn new(fd: Fd, buf: &'a mut Vec<u8>) -> Self {
+    pub fn new(fd: R, buf: &'a mut Vec<u8>) -> Self {
         Self {
             fd,
             buf,
@@ -174,7 +284,7 @@ impl<'a> ReadToVec<'a> {
 }
 
 #[cfg(feature = "alloc")]
-impl Future for ReadToVec<'_> {
+impl<R: AsyncRead + Unpin> Future for ReadToVec<'_, R> {
     type Output = Result<()>;
 
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
@@ -186,26 +296,94 @@ impl Future for ReadToVec<'_> {
                 Err(_) => break Poll::Ready(Err(Errno::NOMEM)),
             }
 
-            let read_size = this.buf.spare_capacity_mut().len();
-
             match ready!(this.fd.poll_read(this.buf.spare_capacity_mut(), cx)) {
                 Ok(0) => break Poll::Ready(Ok(())),
                 Ok(count) => {
                     let len = this.buf.len();
                     unsafe { this.buf.set_len(len + count) }
-
-                    if count < read_size {
-                        match crate::runtime::wake_me_up_on_io(
-                            PollFd::new(this.fd, PollFlags::IN),
-                            cx.waker().clone(),
-                        ) {
-                            Ok(_) => break Poll::Pending,
-                            Err(err) => break Poll::Ready(Err(err.into())),
-                        }
-                    }
                 }
                 Err(err) => break Poll::Ready(Err(err)),
             }
         }
     }
 }
+
+/// A future that transfers a range of a file to a file descriptor with `sendfile`, until the
+/// whole range has been sent or the end of the file is reached.
+///
+/// # Notes
+///
+/// This future assumes that the output file descriptor is in non-blocking mode.
+#[cfg(target_os = "linux")]
+#[doc(alias = "sendfile")]
+#[derive(Debug, Clone)]
+pub struct SendFile {
+    /// The file whose data is sent.
+    file: Fd,
+    /// The file descriptor the data is sent to.
+    ///
+    /// It is expected to be in non-blocking mode.
+    out: Fd,
+    /// The offset of the next byte of `file` to send.
+    offset: u64,
+    /// The number of bytes that must still be sent.
+    remaining: usize,
+    /// The number of bytes sent so far.
+    sent: usize,
+}
+
+#[cfg(target_os = "linux")]
+impl SendFile {
+    /// Creates a new [`SendFile`] future that sends `len` bytes of `file`, starting at
+    /// `offset`, to `out`.
+    pub fn new(file: Fd, out: Fd, offset: u64, len: usize) -> Self {
+        Self {
+            file,
+            out,
+            offset,
+            remaining: len,
+            sent: 0,
+        }
+    }
+
+    /// Returns the offset of the next byte of the file that will be sent.
+    #[inline]
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Returns the number of bytes sent so far.
+    #[inline]
+    pub fn sent(&self) -> usize {
+        self.sent
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl Future for SendFile {
+    /// The total number of bytes sent, which is less than the requested length if the end of
+    /// the file was reached.
+    type Output = Result<usize>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        while this.remaining != 0 {
+            match ready!(this.file.poll_send_file_to(
+                this.out,
+                Some(&mut this.offset),
+                this.remaining,
+                cx
+            )) {
+                Ok(0) => break,
+                Ok(count) => {
+                    this.remaining -= count;
+                    this.sent += count;
+                }
+                Err(err) => return Poll::Ready(Err(err)),
+            }
+        }
+
+        Poll::Ready(Ok(this.sent))
+    }
+}
@@ -3,12 +3,16 @@ compile_error!("The feature `futures` cannot be used without a runtime.");
 
 #[cfg(feature = "collections")]
 mod collections;
+#[cfg(target_os = "linux")]
+mod event;
 mod io;
 mod net;
 mod time;
 
 #[cfg(feature = "collections")]
 pub use self::collections::*;
+#[cfg(target_os = "linux")]
+pub use self::event::*;
 pub use self::io::*;
 pub use self::net::*;
 pub use self::time::*;
@@ -0,0 +1,345 @@
+//! Traits to read and write data generically.
+//!
+//! The [`Read`] and [`Write`] traits abstract over the things that bytes can be read from or
+//! written to. They are implemented for file descriptors, but also for in-memory buffers, which
+//! makes it possible to feed a parser with a byte slice instead of a real file.
+//!
+//! Like [`Fd`], most readers and writers are cheap to copy or borrow. Functions that take a
+//! reader by value (such as [`ReadBuffer::read_until`]) can be given a mutable reference to it
+//! when the reader must be used again afterwards.
+//!
+//! [`ReadBuffer::read_until`]: crate::collections::ReadBuffer::read_until
+
+use core::fmt;
+use core::mem::MaybeUninit;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::errno::retry_on_interrupt;
+use crate::{Errno, Fd, File, Result};
+
+/// A source of bytes.
+pub trait Read {
+    /// Reads some amount of data into the provided buffer.
+    ///
+    /// # Returns
+    ///
+    /// The number of bytes that were initialized at the start of `buf`. `0` means that the
+    /// reader is exhausted (or that `buf` is empty).
+    fn read(&mut self, buf: &mut [MaybeUninit<u8>]) -> Result<usize>;
+
+    /// Reads the remaining data of the reader into `vec`, until it is exhausted or an error
+    /// occurs.
+    #[cfg(feature = "alloc")]
+    fn read_to_vec(&mut self, vec: &mut Vec<u8>) -> Result<()> {
+        let mut batch_size = 64;
+
+        loop {
+            vec.try_reserve(batch_size).map_err(|_| Errno::NOMEM)?;
+
+            let count = retry_on_interrupt(|| self.read(vec.spare_capacity_mut()))?;
+            if count == 0 {
+                return Ok(());
+            }
+            unsafe { vec.set_len(vec.len() + count) };
+
+            batch_size = batch_size.saturating_mul(2);
+        }
+    }
+}
+
+/// A sink of bytes.
+pub trait Write {
+    /// Writes some amount of the provided data.
+    ///
+    /// # Returns
+    ///
+    /// The number of bytes of `data` that were written.
+    fn write(&mut self, data: &[u8]) -> Result<usize>;
+
+    /// Writes the entire contents of `data`.
+    ///
+    /// # Errors
+    ///
+    /// If the writer stops accepting data before everything has been written, this function
+    /// fails with [`Errno::NOSPC`].
+    fn write_all(&mut self, mut data: &[u8]) -> Result<()> {
+        while !data.is_empty() {
+            match retry_on_interrupt(|| self.write(data))? {
+                0 => return Err(Errno::NOSPC),
+                count => data = unsafe { data.get_unchecked(count..) },
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes the provided arguments, preserving the error that might occur.
+    ///
+    /// This is what allows the [`write!`] macro to be used with any writer.
+    fn write_fmt(&mut self, args: fmt::Arguments) -> Result<()> {
+        /// An adapter that keeps track of I/O errors instead of discarding them.
+        struct Adapter<'a, W: ?Sized> {
+            /// The writer to write to.
+            inner: &'a mut W,
+            /// An error that might have occurred while writing.
+            err: Errno,
+        }
+
+        impl<W: ?Sized + Write> fmt::Write for Adapter<'_, W> {
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                self.inner.write_all(s.as_bytes()).map_err(|err| {
+                    self.err = err;
+                    fmt::Error
+                })
+            }
+        }
+
+        let mut output = Adapter {
+            inner: self,
+            err: Errno::SUCCESS,
+        };
+
+        fmt::write(&mut output, args).map_err(|_| output.err)
+    }
+
+    /// Makes sure that the data buffered by the writer, if any, reaches its destination.
+    #[inline]
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// An asynchronous source of bytes.
+#[cfg(feature = "rt-single-thread")]
+pub trait AsyncRead {
+    /// Attempts to read some amount of data into the provided buffer.
+    ///
+    /// If no data is available yet, this function returns `Pending` and schedules the current
+    /// task to be woken up when it is.
+    ///
+    /// # Returns
+    ///
+    /// The number of bytes that were initialized at the start of `buf`, as in [`Read::read`].
+    fn poll_read(
+        &mut self,
+        buf: &mut [MaybeUninit<u8>],
+        cx: &mut core::task::Context,
+    ) -> core::task::Poll<Result<usize>>;
+}
+
+/// An asynchronous sink of bytes.
+#[cfg(feature = "rt-single-thread")]
+pub trait AsyncWrite {
+    /// Attempts to write some amount of the provided data.
+    ///
+    /// If no data can be accepted yet, this function returns `Pending` and schedules the current
+    /// task to be woken up when it can.
+    ///
+    /// # Returns
+    ///
+    /// The number of bytes of `data` that were written, as in [`Write::write`].
+    fn poll_write(
+        &mut self,
+        data: &[u8],
+        cx: &mut core::task::Context,
+    ) -> core::task::Poll<Result<usize>>;
+}
+
+impl<R: ?Sized + Read> Read for &mut R {
+    #[inline]
+    fn read(&mut self, buf: &mut [MaybeUninit<u8>]) -> Result<usize> {
+        (**self).read(buf)
+    }
+}
+
+impl<W: ?Sized + Write> Write for &mut W {
+    #[inline]
+    fn write(&mut self, data: &[u8]) -> Result<usize> {
+        (**self).write(data)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<()> {
+        (**self).flush()
+    }
+}
+
+impl Read for Fd {
+    #[inline]
+    fn read(&mut self, buf: &mut [MaybeUninit<u8>]) -> Result<usize> {
+        Fd::read(*self, buf)
+    }
+}
+
+impl Write for Fd {
+    #[inline]
+    fn write(&mut self, data: &[u8]) -> Result<usize> {
+        Fd::write(*self, data)
+    }
+}
+
+impl Read for File {
+    #[inline]
+    fn read(&mut self, buf: &mut [MaybeUninit<u8>]) -> Result<usize> {
+        Fd::read(**self, buf)
+    }
+}
+
+impl Write for File {
+    #[inline]
+    fn write(&mut self, data: &[u8]) -> Result<usize> {
+        Fd::write(**self, data)
+    }
+}
+
+/// Reading from a byte slice copies its first bytes and advances it past them.
+impl Read for &[u8] {
+    fn read(&mut self, buf: &mut [MaybeUninit<u8>]) -> Result<usize> {
+        let count = Ord::min(buf.len(), self.len());
+        let (head, tail) = self.split_at(count);
+
+        unsafe {
+            buf.as_mut_ptr()
+                .cast::<u8>()
+                .copy_from_nonoverlapping(head.as_ptr(), count);
+        }
+
+        *self = tail;
+        Ok(count)
+    }
+}
+
+/// Writing to a mutable byte slice overwrites its first bytes and advances it past them.
+///
+/// Once the slice is empty, writes succeed with a length of `0`.
+impl Write for &mut [u8] {
+    fn write(&mut self, data: &[u8]) -> Result<usize> {
+        let count = Ord::min(data.len(), self.len());
+        let (head, tail) = core::mem::take(self).split_at_mut(count);
+        head.copy_from_slice(&data[..count]);
+        *self = tail;
+        Ok(count)
+    }
+}
+
+/// Writing to a vector appends the data to it.
+#[cfg(feature = "alloc")]
+impl Write for Vec<u8> {
+    #[inline]
+    fn write(&mut self, data: &[u8]) -> Result<usize> {
+        self.try_reserve(data.len()).map_err(|_| Errno::NOMEM)?;
+        self.extend_from_slice(data);
+        Ok(data.len())
+    }
+}
+
+#[cfg(feature = "rt-single-thread")]
+impl<R: ?Sized + AsyncRead> AsyncRead for &mut R {
+    #[inline]
+    fn poll_read(
+        &mut self,
+        buf: &mut [MaybeUninit<u8>],
+        cx: &mut core::task::Context,
+    ) -> core::task::Poll<Result<usize>> {
+        (**self).poll_read(buf, cx)
+    }
+}
+
+#[cfg(feature = "rt-single-thread")]
+impl<W: ?Sized + AsyncWrite> AsyncWrite for &mut W {
+    #[inline]
+    fn poll_write(
+        &mut self,
+        data: &[u8],
+        cx: &mut core::task::Context,
+    ) -> core::task::Poll<Result<usize>> {
+        (**self).poll_write(data, cx)
+    }
+}
+
+#[cfg(feature = "rt-single-thread")]
+impl AsyncRead for Fd {
+    #[inline]
+    fn poll_read(
+        &mut self,
+        buf: &mut [MaybeUninit<u8>],
+        cx: &mut core::task::Context,
+    ) -> core::task::Poll<Result<usize>> {
+        Fd::poll_read(*self, buf, cx)
+    }
+}
+
+#[cfg(feature = "rt-single-thread")]
+impl AsyncWrite for Fd {
+    #[inline]
+    fn poll_write(
+        &mut self,
+        data: &[u8],
+        cx: &mut core::task::Context,
+    ) -> core::task::Poll<Result<usize>> {
+        Fd::poll_write(*self, data, cx)
+    }
+}
+
+#[cfg(feature = "rt-single-thread")]
+impl AsyncRead for File {
+    #[inline]
+    fn poll_read(
+        &mut self,
+        buf: &mut [MaybeUninit<u8>],
+        cx: &mut core::task::Context,
+    ) -> core::task::Poll<Result<usize>> {
+        Fd::poll_read(**self, buf, cx)
+    }
+}
+
+#[cfg(feature = "rt-single-thread")]
+impl AsyncWrite for File {
+    #[inline]
+    fn poll_write(
+        &mut self,
+        data: &[u8],
+        cx: &mut core::task::Context,
+    ) -> core::task::Poll<Result<usize>> {
+        Fd::poll_write(**self, data, cx)
+    }
+}
+
+/// In-memory buffers never block: polling them always completes immediately.
+#[cfg(feature = "rt-single-thread")]
+impl AsyncRead for &[u8] {
+    #[inline]
+    fn poll_read(
+        &mut self,
+        buf: &mut [MaybeUninit<u8>],
+        _cx: &mut core::task::Context,
+    ) -> core::task::Poll<Result<usize>> {
+        core::task::Poll::Ready(Read::read(self, buf))
+    }
+}
+
+#[cfg(feature = "rt-single-thread")]
+impl AsyncWrite for &mut [u8] {
+    #[inline]
+    fn poll_write(
+        &mut self,
+        data: &[u8],
+        _cx: &mut core::task::Context,
+    ) -> core::task::Poll<Result<usize>> {
+        core::task::Poll::Ready(Write::write(self, data))
+    }
+}
+
+#[cfg(all(feature = "rt-single-thread", feature = "alloc"))]
+impl AsyncWrite for Vec<u8> {
+    #[inline]
+    fn poll_write(
+        &mut self,
+        data: &[u8],
+        _cx: &mut core::task::Context,
+    ) -> core::task::Poll<Result<usize>> {
+        core::task::Poll::Ready(Write::write(self, data))
+    }
+}
@@ -16,6 +16,7 @@ extern crate alloc;
 mod alloc_ext;
 mod ctor;
 mod errno;
+mod error;
 #[cfg(feature = "restrict-functions")]
 mod fake_libc;
 #[cfg(feature = "global-allocator")]
@@ -28,14 +29,18 @@ mod time;
 mod user;
 
 pub mod ansi;
+pub mod args;
 pub mod charstar;
 #[cfg(feature = "collections")]
 pub mod collections;
 pub mod dylib;
 pub mod entry_point;
+pub mod env;
 pub mod fd;
+pub mod fs;
 #[cfg(feature = "futures")]
 pub mod futures;
+pub mod io;
 pub mod malloc;
 pub mod mmap;
 pub mod net;
@@ -51,7 +56,9 @@ pub mod utils;
 #[cfg(feature = "alloc")]
 pub use self::alloc_ext::*;
 pub use self::charstar::CharStar;
-pub use self::errno::{Errno, Result};
+pub use self::entry_point::ExitCode;
+pub use self::errno::{restart_on_interrupt, set_restart_on_interrupt, Errno, Result};
+pub use self::error::{Error, ResultExt};
 pub use self::fd::{Fd, File};
 pub use self::memchr_ext::*;
 pub use self::misc::*;
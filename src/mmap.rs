@@ -1,6 +1,9 @@
 //! Virtual-to-physical memory mapping.
 
 use core::ffi::{c_int, c_void};
+use core::fmt;
+use core::ops::{Deref, DerefMut};
+use core::ptr::NonNull;
 
 use bitflags::bitflags;
 
@@ -90,3 +93,420 @@ pub unsafe fn unmap(addr: *mut (), length: usize) -> Result<()> {
         Err(Errno::last())
     }
 }
+
+/// A hint passed to [`Mmap::advise`] about how a mapping will be accessed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Advice {
+    /// No special treatment.
+    #[doc(alias = "MADV_NORMAL")]
+    Normal,
+    /// Pages will be accessed in random order, making read-ahead less useful.
+    #[doc(alias = "MADV_RANDOM")]
+    Random,
+    /// Pages will be accessed in sequential order, making aggressive read-ahead useful.
+    #[doc(alias = "MADV_SEQUENTIAL")]
+    Sequential,
+    /// Pages will be accessed soon, and may be read ahead of time.
+    #[doc(alias = "MADV_WILLNEED")]
+    WillNeed,
+    /// Pages will not be accessed soon.
+    ///
+    /// For private mappings, this may discard the modifications made to the pages, which are
+    /// then filled with zeros (or read again from the file) on the next access.
+    #[doc(alias = "MADV_DONTNEED")]
+    DontNeed,
+}
+
+impl Advice {
+    /// Returns the raw value of this advice.
+    fn to_raw(self) -> c_int {
+        match self {
+            Self::Normal => libc::MADV_NORMAL,
+            Self::Random => libc::MADV_RANDOM,
+            Self::Sequential => libc::MADV_SEQUENTIAL,
+            Self::WillNeed => libc::MADV_WILLNEED,
+            Self::DontNeed => libc::MADV_DONTNEED,
+        }
+    }
+}
+
+/// A mapping owned by [`Mmap`] or [`MmapMut`].
+///
+/// Empty mappings are represented by a dangling pointer, because `mmap` cannot create them.
+struct RawMapping {
+    /// The start of the mapping.
+    ptr: NonNull<u8>,
+    /// The length of the mapping, in bytes.
+    len: usize,
+}
+
+impl RawMapping {
+    /// Creates a new mapping.
+    ///
+    /// # Safety
+    ///
+    /// Same as `mmap`.
+    unsafe fn new(len: usize, prot: Prot, flags: c_int, fd: Fd) -> Result<Self> {
+        if len == 0 {
+            return Ok(Self {
+                ptr: NonNull::dangling(),
+                len: 0,
+            });
+        }
+
+        let ret = unsafe {
+            libc::mmap(
+                core::ptr::null_mut(),
+                len,
+                prot.bits(),
+                flags,
+                fd.to_raw(),
+                0,
+            )
+        };
+
+        if ret == libc::MAP_FAILED {
+            Err(Errno::last())
+        } else {
+            Ok(Self {
+                ptr: unsafe { NonNull::new_unchecked(ret.cast()) },
+                len,
+            })
+        }
+    }
+
+    /// Returns the length that the whole file referred to by `fd` would need to be mapped.
+    fn file_len(fd: Fd) -> Result<usize> {
+        let size = fd.metadata()?.size();
+        usize::try_from(size).map_err(|_| Errno::NOMEM)
+    }
+
+    /// Changes the protection of the mapping.
+    fn protect(&self, prot: Prot) -> Result<()> {
+        if self.len == 0 {
+            return Ok(());
+        }
+
+        let ret = unsafe { libc::mprotect(self.ptr.as_ptr().cast(), self.len, prot.bits()) };
+        if ret == 0 {
+            Ok(())
+        } else {
+            Err(Errno::last())
+        }
+    }
+
+    /// Gives advice about the mapping.
+    fn advise(&self, advice: Advice) -> Result<()> {
+        if self.len == 0 {
+            return Ok(());
+        }
+
+        let ret = unsafe { libc::madvise(self.ptr.as_ptr().cast(), self.len, advice.to_raw()) };
+        if ret == 0 {
+            Ok(())
+        } else {
+            Err(Errno::last())
+        }
+    }
+
+    /// Synchronizes the mapping with its file.
+    fn sync(&self, flags: c_int) -> Result<()> {
+        if self.len == 0 {
+            return Ok(());
+        }
+
+        let ret = unsafe { libc::msync(self.ptr.as_ptr().cast(), self.len, flags) };
+        if ret == 0 {
+            Ok(())
+        } else {
+            Err(Errno::last())
+        }
+    }
+
+    /// Resizes the mapping.
+    #[cfg(target_os = "linux")]
+    fn remap(&mut self, new_len: usize) -> Result<()> {
+        if self.len == 0 || new_len == 0 {
+            return Err(Errno::INVAL);
+        }
+
+        let ret = unsafe {
+            libc::mremap(
+                self.ptr.as_ptr().cast(),
+                self.len,
+                new_len,
+                libc::MREMAP_MAYMOVE,
+            )
+        };
+
+        if ret == libc::MAP_FAILED {
+            Err(Errno::last())
+        } else {
+            self.ptr = unsafe { NonNull::new_unchecked(ret.cast()) };
+            self.len = new_len;
+            Ok(())
+        }
+    }
+}
+
+impl Drop for RawMapping {
+    fn drop(&mut self) {
+        if self.len != 0 {
+            let _ = unsafe { unmap(self.ptr.as_ptr().cast(), self.len) };
+        }
+    }
+}
+
+/// An owned, read-only memory mapping.
+///
+/// The mapping is unmapped when the [`Mmap`] is dropped.
+pub struct Mmap(RawMapping);
+
+unsafe impl Send for Mmap {}
+unsafe impl Sync for Mmap {}
+
+impl Mmap {
+    /// Maps the whole file referred to by `fd` in memory, for reading.
+    ///
+    /// The mapping remains valid after `fd` is closed. An empty file produces an empty
+    /// mapping.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated while it is mapped, by this process or by
+    /// another one. Modifications would be visible through the returned `&[u8]`, and accessing
+    /// a page past the end of a truncated file raises `SIGBUS`.
+    #[doc(alias = "mmap")]
+    pub unsafe fn map_file(fd: Fd) -> Result<Self> {
+        let len = RawMapping::file_len(fd)?;
+        unsafe { RawMapping::new(len, Prot::READ, libc::MAP_PRIVATE, fd).map(Self) }
+    }
+
+    /// Returns the length of the mapping, in bytes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len
+    }
+
+    /// Returns whether the mapping is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.len == 0
+    }
+
+    /// Returns a pointer to the start of the mapping.
+    #[inline]
+    pub fn as_ptr(&self) -> *const u8 {
+        self.0.ptr.as_ptr()
+    }
+
+    /// Tells the kernel how the mapping will be accessed.
+    #[inline]
+    #[doc(alias = "madvise")]
+    pub fn advise(&self, advice: Advice) -> Result<()> {
+        self.0.advise(advice)
+    }
+
+    /// Makes the mapping writable.
+    ///
+    /// Modifications made to a mapped file are private to the process, and are never written
+    /// back to the file.
+    #[doc(alias = "mprotect")]
+    pub fn make_mut(self) -> Result<MmapMut> {
+        self.0.protect(Prot::READ | Prot::WRITE)?;
+        Ok(MmapMut(self.0))
+    }
+}
+
+impl Deref for Mmap {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        unsafe { core::slice::from_raw_parts(self.0.ptr.as_ptr(), self.0.len) }
+    }
+}
+
+impl AsRef<[u8]> for Mmap {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+impl fmt::Debug for Mmap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Mmap")
+            .field("ptr", &self.0.ptr)
+            .field("len", &self.0.len)
+            .finish()
+    }
+}
+
+/// An owned, writable memory mapping.
+///
+/// The mapping is unmapped when the [`MmapMut`] is dropped.
+pub struct MmapMut(RawMapping);
+
+unsafe impl Send for MmapMut {}
+unsafe impl Sync for MmapMut {}
+
+impl MmapMut {
+    /// Creates an anonymous mapping of `len` bytes, initially filled with zeros.
+    #[doc(alias = "mmap")]
+    pub fn anonymous(len: usize) -> Result<Self> {
+        unsafe {
+            RawMapping::new(
+                len,
+                Prot::READ | Prot::WRITE,
+                libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+                Fd::from_raw(-1),
+            )
+            .map(Self)
+        }
+    }
+
+    /// Maps the whole file referred to by `fd` in memory, for reading and writing.
+    ///
+    /// The mapping is shared: modifications are written back to the file (see
+    /// [`flush`](Self::flush)), and are visible to the other processes that map it. `fd` must
+    /// be open for reading and writing. The mapping remains valid after `fd` is closed.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated while it is mapped, except through the
+    /// returned mapping. Accessing a page past the end of a truncated file raises `SIGBUS`.
+    #[doc(alias = "mmap")]
+    pub unsafe fn map_file(fd: Fd) -> Result<Self> {
+        let len = RawMapping::file_len(fd)?;
+        unsafe { RawMapping::new(len, Prot::READ | Prot::WRITE, libc::MAP_SHARED, fd).map(Self) }
+    }
+
+    /// Returns the length of the mapping, in bytes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len
+    }
+
+    /// Returns whether the mapping is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.len == 0
+    }
+
+    /// Returns a pointer to the start of the mapping.
+    #[inline]
+    pub fn as_ptr(&self) -> *const u8 {
+        self.0.ptr.as_ptr()
+    }
+
+    /// Returns a mutable pointer to the start of the mapping.
+    #[inline]
+    pub fn as_mut_ptr(&mut self) -> *mut u8 {
+        self.0.ptr.as_ptr()
+    }
+
+    /// Tells the kernel how the mapping will be accessed.
+    #[inline]
+    #[doc(alias = "madvise")]
+    pub fn advise(&self, advice: Advice) -> Result<()> {
+        self.0.advise(advice)
+    }
+
+    /// Changes the protection of the mapping.
+    ///
+    /// [`make_read_only`](Self::make_read_only) and [`Mmap::make_mut`] should be preferred when
+    /// they are enough, as they cannot make the mapping inaccessible.
+    ///
+    /// # Safety
+    ///
+    /// As long as `prot` does not include both [`Prot::READ`] and [`Prot::WRITE`], the mapping
+    /// must not be accessed through this [`MmapMut`] (including through [`Deref`],
+    /// [`DerefMut`], [`AsRef`] and [`AsMut`]), because the references it hands out assume
+    /// that the memory is readable and writable. The protection must be restored before the
+    /// mapping is accessed again.
+    #[inline]
+    #[doc(alias = "mprotect")]
+    pub unsafe fn protect(&mut self, prot: Prot) -> Result<()> {
+        self.0.protect(prot)
+    }
+
+    /// Makes the mapping read-only.
+    #[doc(alias = "mprotect")]
+    pub fn make_read_only(self) -> Result<Mmap> {
+        self.0.protect(Prot::READ)?;
+        Ok(Mmap(self.0))
+    }
+
+    /// Writes the modifications made to a file mapping back to the file, waiting for the
+    /// operation to complete.
+    ///
+    /// This does nothing for anonymous mappings.
+    #[inline]
+    #[doc(alias = "msync")]
+    pub fn flush(&self) -> Result<()> {
+        self.0.sync(libc::MS_SYNC)
+    }
+
+    /// Like [`flush`](Self::flush), but only schedules the write without waiting for it.
+    #[inline]
+    #[doc(alias = "msync")]
+    pub fn flush_async(&self) -> Result<()> {
+        self.0.sync(libc::MS_ASYNC)
+    }
+
+    /// Grows or shrinks the mapping to `new_len` bytes, moving it if needed.
+    ///
+    /// When an anonymous mapping grows, the new bytes are filled with zeros. For file
+    /// mappings, accessing a page past the end of the file raises `SIGBUS`.
+    ///
+    /// # Errors
+    ///
+    /// This function fails with [`Errno::INVAL`] if the mapping is empty or if `new_len` is
+    /// zero.
+    #[inline]
+    #[cfg(target_os = "linux")]
+    #[doc(alias = "mremap")]
+    pub fn remap(&mut self, new_len: usize) -> Result<()> {
+        self.0.remap(new_len)
+    }
+}
+
+impl Deref for MmapMut {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        unsafe { core::slice::from_raw_parts(self.0.ptr.as_ptr(), self.0.len) }
+    }
+}
+
+impl DerefMut for MmapMut {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { core::slice::from_raw_parts_mut(self.0.ptr.as_ptr(), self.0.len) }
+    }
+}
+
+impl AsRef<[u8]> for MmapMut {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+impl AsMut<[u8]> for MmapMut {
+    #[inline]
+    fn as_mut(&mut self) -> &mut [u8] {
+        self
+    }
+}
+
+impl fmt::Debug for MmapMut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MmapMut")
+            .field("ptr", &self.0.ptr)
+            .field("len", &self.0.len)
+            .finish()
+    }
+}
@@ -2,6 +2,7 @@ use core::ffi::c_int;
 
 use bitflags::bitflags;
 
+use crate::errno::retry_on_interrupt;
 use crate::{Result, Signal};
 
 bitflags! {
@@ -85,13 +86,16 @@ impl Pid {
     #[doc(alias = "waitpid")]
     pub fn wait_any(opts: WaitOptions) -> Result<(Pid, ExitStatus)> {
         let mut status = 0;
-        let ret = unsafe { libc::waitpid(-1, &mut status, opts.bits()) };
+        let ret = retry_on_interrupt(|| {
+            let ret = unsafe { libc::waitpid(-1, &mut status, opts.bits()) };
+            if ret == -1 {
+                Err(crate::Errno::last())
+            } else {
+                Ok(ret)
+            }
+        })?;
 
-        if ret == -1 {
-            Err(crate::Errno::last())
-        } else {
-            Ok((Pid::from_raw(ret), ExitStatus::from_raw(status)))
-        }
+        Ok((Pid::from_raw(ret), ExitStatus::from_raw(status)))
     }
 
     /// Waits until this process changes state.
@@ -104,13 +108,16 @@ impl Pid {
     #[doc(alias = "waitpid")]
     pub fn wait(self, opts: WaitOptions) -> Result<ExitStatus> {
         let mut status = 0;
-        let ret = unsafe { libc::waitpid(self.as_raw(), &mut status, opts.bits()) };
+        retry_on_interrupt(|| {
+            let ret = unsafe { libc::waitpid(self.as_raw(), &mut status, opts.bits()) };
+            if ret == -1 {
+                Err(crate::Errno::last())
+            } else {
+                Ok(())
+            }
+        })?;
 
-        if ret == -1 {
-            Err(crate::Errno::last())
-        } else {
-            Ok(ExitStatus::from_raw(status))
-        }
+        Ok(ExitStatus::from_raw(status))
     }
 
     /// Sends a signal to the current process.
@@ -19,6 +19,14 @@ impl Signal {
     pub const INT: Self = Self(libc::SIGINT);
     /// The `SIGQUIT` signal.
     pub const QUIT: Self = Self(libc::SIGQUIT);
+    /// The `SIGHUP` signal.
+    pub const HUP: Self = Self(libc::SIGHUP);
+    /// The `SIGCHLD` signal.
+    pub const CHLD: Self = Self(libc::SIGCHLD);
+    /// The `SIGUSR1` signal.
+    pub const USR1: Self = Self(libc::SIGUSR1);
+    /// The `SIGUSR2` signal.
+    pub const USR2: Self = Self(libc::SIGUSR2);
 }
 
 impl Signal {
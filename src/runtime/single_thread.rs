@@ -144,6 +144,14 @@ pub fn run_until_idle() -> crate::Result<usize> {
     Ok(unsafe { TASKS.update(|tasks| tasks.len()) })
 }
 
+/// Returns whether the runtime can still make progress.
+///
+/// This is `false` when no task is ready to run and no waker is waiting for I/O or for a timer.
+/// Pending tasks will then never complete, unless they are woken up from outside of the runtime.
+pub fn can_make_progress() -> bool {
+    unsafe { TASKS.update(|tasks| tasks.has_ready_tasks()) || WAKER.update(|w| !w.is_empty()) }
+}
+
 /// Schedules the provided waker to be consumed when `pollfd` becomes ready.
 #[inline]
 pub fn wake_me_up_on_io(pollfd: PollFd, waker: Waker) -> Result<(), OutOfMemory> {
@@ -73,6 +73,12 @@ impl<'a> Tasks<'a> {
         self.len
     }
 
+    /// Returns whether some task is ready to run.
+    #[inline]
+    pub fn has_ready_tasks(&self) -> bool {
+        self.ready_list_head != usize::MAX
+    }
+
     /// Inserts a new task in the list.
     pub fn insert(&mut self, task: DynTask<'a>) -> Result<(), OutOfMemory> {
         let new_slot = TaskSlot::Ready {
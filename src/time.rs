@@ -26,6 +26,12 @@ impl Clock {
     /// incremental adjustments performed by NTP.
     pub const MONOTONIC: Clock = Clock(libc::CLOCK_MONOTONIC);
 
+    /// Returns the raw `clockid_t` value of this clock.
+    #[inline]
+    pub const fn as_raw(self) -> libc::clockid_t {
+        self.0
+    }
+
     /// Returns the current instant associated with this clock.
     #[doc(alias = "clock_gettime")]
     pub fn get(self) -> Instant {
@@ -7,6 +7,18 @@ impl Uid {
     /// Returns the user ID of the root user.
     pub const ROOT: Self = Self(0);
 
+    /// Creates a new [`Uid`] from the provided raw value.
+    #[inline(always)]
+    pub const fn from_raw(raw: libc::uid_t) -> Self {
+        Self(raw)
+    }
+
+    /// Returns the raw value of this [`Uid`].
+    #[inline(always)]
+    pub const fn to_raw(self) -> libc::uid_t {
+        self.0
+    }
+
     /// Returns the real user ID of the calling process.
     #[inline]
     #[doc(alias = "getuid")]
@@ -27,3 +39,39 @@ impl Uid {
         self.0 == Self::ROOT.0
     }
 }
+
+/// A group identifier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[doc(alias = "gid_t")]
+pub struct Gid(libc::gid_t);
+
+impl Gid {
+    /// Returns the group ID of the root group.
+    pub const ROOT: Self = Self(0);
+
+    /// Creates a new [`Gid`] from the provided raw value.
+    #[inline(always)]
+    pub const fn from_raw(raw: libc::gid_t) -> Self {
+        Self(raw)
+    }
+
+    /// Returns the raw value of this [`Gid`].
+    #[inline(always)]
+    pub const fn to_raw(self) -> libc::gid_t {
+        self.0
+    }
+
+    /// Returns the real group ID of the calling process.
+    #[inline]
+    #[doc(alias = "getgid")]
+    pub fn current() -> Self {
+        Self(unsafe { libc::getgid() })
+    }
+
+    /// Returns the effective group ID of the calling process.
+    #[inline]
+    #[doc(alias = "getegid")]
+    pub fn effective() -> Self {
+        Self(unsafe { libc::getegid() })
+    }
+}
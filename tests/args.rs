@@ -0,0 +1,138 @@
+use ft::args::{Arg, Error, Opt, OptName, Parser, Usage};
+use ft::{charstar, CharStar};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Flag {
+    All,
+    Brief,
+    Output,
+    Count,
+}
+
+const OPTS: &[Opt<Flag>] = &[
+    Opt::new(Flag::All)
+        .short(b'a')
+        .long("all")
+        .help("show everything"),
+    Opt::new(Flag::Brief).short(b'b').help("be brief"),
+    Opt::new(Flag::Output)
+        .short(b'o')
+        .long("out")
+        .value("FILE")
+        .help("write to FILE"),
+    Opt::new(Flag::Count).long("count").value("N"),
+];
+
+/// Parses `argv` and returns a textual summary of the arguments found.
+fn parse(argv: &[&CharStar]) -> Vec<String> {
+    Parser::new(OPTS, argv)
+        .map(|arg| match arg {
+            Ok(Arg::Opt(m)) => match m.value {
+                Some(value) => format!("{:?}={}", m.id, value),
+                None => format!("{:?}", m.id),
+            },
+            Ok(Arg::Positional(p)) => format!("pos:{p}"),
+            Err(err) => format!("err:{err}"),
+        })
+        .collect()
+}
+
+#[test]
+fn short_cluster() {
+    let argv = [charstar!("prog"), charstar!("-ab"), charstar!("file")];
+    assert_eq!(parse(&argv), ["All", "Brief", "pos:file"]);
+}
+
+#[test]
+fn short_values() {
+    let argv = [
+        charstar!("prog"),
+        charstar!("-bofoo"),
+        charstar!("-o"),
+        charstar!("bar"),
+    ];
+    assert_eq!(parse(&argv), ["Brief", "Output=foo", "Output=bar"]);
+}
+
+#[test]
+fn long_values() {
+    let argv = [
+        charstar!("prog"),
+        charstar!("--out=foo"),
+        charstar!("--out"),
+        charstar!("bar"),
+        charstar!("--all"),
+    ];
+    assert_eq!(parse(&argv), ["Output=foo", "Output=bar", "All"]);
+}
+
+#[test]
+fn end_of_options() {
+    let argv = [
+        charstar!("prog"),
+        charstar!("-"),
+        charstar!("--"),
+        charstar!("-a"),
+        charstar!("--all"),
+    ];
+    assert_eq!(parse(&argv), ["pos:-", "pos:-a", "pos:--all"]);
+}
+
+#[test]
+fn errors() {
+    let argv = [
+        charstar!("prog"),
+        charstar!("-xa"),
+        charstar!("--nope"),
+        charstar!("--all=1"),
+        charstar!("-o"),
+    ];
+    assert_eq!(
+        parse(&argv),
+        [
+            "err:unknown option '-x'",
+            "All",
+            "err:unknown option '--nope'",
+            "err:option '--all' does not take a value",
+            "err:option '-o' requires a value",
+        ]
+    );
+}
+
+#[test]
+fn typed_values() {
+    let argv = [
+        charstar!("prog"),
+        charstar!("--count=42"),
+        charstar!("--count=x"),
+    ];
+    let mut parser = Parser::new(OPTS, &argv);
+
+    let Some(Ok(Arg::Opt(m))) = parser.next() else {
+        panic!("expected an option");
+    };
+    assert_eq!(m.parse::<u32>().unwrap(), 42);
+
+    let Some(Ok(Arg::Opt(m))) = parser.next() else {
+        panic!("expected an option");
+    };
+    assert!(matches!(
+        m.parse::<u32>(),
+        Err(Error::InvalidValue(OptName::Long(b"count"), _))
+    ));
+}
+
+#[test]
+fn usage() {
+    let usage = Usage::new(charstar!("prog"), OPTS, "[FILE]...").to_string();
+    assert_eq!(
+        usage,
+        "usage: prog [OPTIONS] [FILE]...\n\
+         \n\
+         options:\n  \
+         -a, --all       show everything\n  \
+         -b              be brief\n  \
+         -o, --out FILE  write to FILE\n      \
+         --count N\n"
+    );
+}
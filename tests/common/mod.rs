@@ -0,0 +1,77 @@
+//! Fixtures shared by the integration tests.
+
+#![allow(dead_code)]
+
+use std::ffi::OsStr;
+use std::ops::Deref;
+use std::os::unix::ffi::OsStrExt;
+use std::path::Path;
+
+use ft::charstar::CharStarBox;
+use ft::fd::{Mode, OpenFlags};
+use ft::{CharStar, Fd, File};
+
+/// A path in the temporary directory that is unique to this process.
+///
+/// Whatever exists at that path when the [`TempPath`] is dropped is removed, so that failing
+/// tests do not leave files behind.
+pub struct TempPath(CharStarBox);
+
+impl TempPath {
+    /// Returns the path of `name` inside of this path.
+    pub fn join(&self, name: &str) -> CharStarBox {
+        let mut path = self.0.as_bytes().to_vec();
+        path.push(b'/');
+        path.extend_from_slice(name.as_bytes());
+        CharStarBox::from_bytes(&path).unwrap()
+    }
+}
+
+impl Deref for TempPath {
+    type Target = CharStarBox;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Drop for TempPath {
+    fn drop(&mut self) {
+        let path = Path::new(OsStr::from_bytes(self.0.as_bytes()));
+        match std::fs::symlink_metadata(path) {
+            Ok(meta) if meta.is_dir() => {
+                let _ = std::fs::remove_dir_all(path);
+            }
+            Ok(_) => {
+                let _ = std::fs::remove_file(path);
+            }
+            Err(_) => (),
+        }
+    }
+}
+
+/// Returns a path in the temporary directory that is unique to this process.
+pub fn temp_path(name: &str) -> TempPath {
+    let path = format!("/tmp/ft-test-{}-{name}", std::process::id());
+    TempPath(CharStarBox::from_bytes(path.as_bytes()).unwrap())
+}
+
+/// Opens the file at `path` with the provided flags, creating it if needed with permissions
+/// that only allow its owner to read and write it.
+pub fn create_file(path: &CharStar, flags: OpenFlags) -> File {
+    let fd = Fd::open_with_mode(
+        path,
+        flags | OpenFlags::CREATE,
+        Mode::OWNER_READ | Mode::OWNER_WRITE,
+    )
+    .unwrap();
+    File::from_fd(fd)
+}
+
+/// Creates a temporary file that contains `contents`, opened for reading and writing.
+pub fn temp_file(name: &str, contents: &[u8]) -> (TempPath, File) {
+    let path = temp_path(name);
+    let file = create_file(&path, OpenFlags::READ_WRITE | OpenFlags::TRUNCATE);
+    file.write_all(contents).unwrap();
+    (path, file)
+}
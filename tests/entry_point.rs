@@ -0,0 +1,134 @@
+use core::ffi::{c_char, c_int};
+use core::future::Future;
+use core::mem::MaybeUninit;
+
+use ft::entry_point::{ExitCode, Terminate};
+use ft::fd::PipeFlags;
+use ft::process::{fork, ExitStatus, Fork, WaitOptions};
+use ft::{CharStar, Errno, Fd, File};
+
+/// The `argv` and `envp` arrays passed to the entry points.
+const ARGV: &[*const c_char] = &[c"prog".as_ptr(), core::ptr::null()];
+const ENVP: &[*const c_char] = &[c"HOME=/home/user".as_ptr(), core::ptr::null()];
+
+/// Runs `f` in a child process and exits with the code it returns.
+///
+/// # Returns
+///
+/// The exit status of the child, and what it wrote to the standard error stream.
+fn run_in_child(f: impl FnOnce() -> c_int) -> (ExitStatus, Vec<u8>) {
+    let (read, write) = File::pipe(PipeFlags::empty()).unwrap();
+
+    match fork().unwrap() {
+        Fork::Child => {
+            drop(read);
+            write.duplicate_to(Fd::STDERR, false).unwrap();
+            drop(write);
+            std::process::exit(f());
+        }
+        Fork::Parent(child) => {
+            drop(write);
+            let mut stderr = Vec::new();
+            read.read_to_vec(&mut stderr).unwrap();
+            (child.wait(WaitOptions::empty()).unwrap(), stderr)
+        }
+    }
+}
+
+/// Calls `main` as the entry point of the program.
+fn call<R: Terminate>(
+    main: fn(&'static [&'static CharStar], &'static [&'static CharStar]) -> R,
+) -> c_int {
+    unsafe { ft::__private::entry_point::call(main, ARGV.as_ptr(), ENVP.as_ptr()) }
+}
+
+/// Calls `main` as the asynchronous entry point of the program.
+fn call_async<Fut>(
+    main: fn(&'static [&'static CharStar], &'static [&'static CharStar]) -> Fut,
+) -> c_int
+where
+    Fut: 'static + Future,
+    Fut::Output: Terminate,
+{
+    unsafe { ft::__private::entry_point::call_async(main, ARGV.as_ptr(), ENVP.as_ptr()) }
+}
+
+#[test]
+fn async_main() {
+    let (status, stderr) = run_in_child(|| {
+        call_async(|args, env| async move {
+            let (read, write) = File::pipe(PipeFlags::NON_BLOCKING).unwrap();
+
+            // The main task is only woken up once this one ran.
+            ft::runtime::spawn(async move {
+                write.write_all(b"x").unwrap();
+            });
+
+            let mut buf = [MaybeUninit::uninit(); 1];
+            let count = read.async_read(&mut buf).await.unwrap();
+            (args.len() + env.len() + count) as c_int
+        })
+    });
+
+    assert!(matches!(status, ExitStatus::Exited(3)));
+    assert_eq!(stderr, b"");
+}
+
+#[test]
+fn async_main_deadlock() {
+    let (status, stderr) = run_in_child(|| call_async(|_, _| core::future::pending::<()>()));
+
+    assert!(matches!(status, ExitStatus::Exited(1)));
+    assert_eq!(stderr, b"prog: Resource deadlock avoided\n");
+}
+
+#[test]
+fn exit_code_from_errno() {
+    assert_eq!(ExitCode::from_errno(Errno::SUCCESS), ExitCode::SUCCESS);
+    assert_eq!(ExitCode::from_errno(Errno::NOENT), ExitCode::NOINPUT);
+    assert_eq!(ExitCode::from_errno(Errno::ACCES), ExitCode::NOPERM);
+    assert_eq!(ExitCode::from_errno(Errno::INVAL), ExitCode::USAGE);
+    assert_eq!(ExitCode::from_errno(Errno::NOSPC), ExitCode::CANTCREAT);
+    assert_eq!(ExitCode::from_errno(Errno::DEADLK), ExitCode::FAILURE);
+    assert_eq!(ExitCode::from(Errno::NOMEM), ExitCode::OSERR);
+    assert!(ExitCode::from(0).is_success());
+    assert!(!ExitCode::FAILURE.is_success());
+}
+
+#[test]
+fn terminate() {
+    assert_eq!(3.terminate(), 3);
+    assert_eq!(().terminate(), 0);
+    assert_eq!(Ok::<u8, ExitCode>(2).terminate(), 2);
+    assert_eq!(Err::<(), _>(ExitCode::USAGE).terminate(), 64);
+    assert_eq!(Errno::NOENT.terminate(), 66);
+}
+
+#[test]
+fn main_returns_error() {
+    let (status, stderr) = run_in_child(|| call(|_, _| Err::<(), _>("invalid input")));
+    assert!(matches!(status, ExitStatus::Exited(1)));
+    assert_eq!(stderr, b"prog: invalid input\n");
+
+    let (status, stderr) = run_in_child(|| call(|_, _| Err::<(), _>(Errno::NOENT)));
+    assert!(matches!(status, ExitStatus::Exited(1)));
+    assert_eq!(stderr, b"prog: No such file or directory\n");
+
+    let (status, stderr) = run_in_child(|| call(|_, _| Err::<(), _>(ExitCode::USAGE)));
+    assert!(matches!(status, ExitStatus::Exited(64)));
+    assert_eq!(stderr, b"");
+
+    let (status, stderr) = run_in_child(|| call(|_, _| Errno::NOENT));
+    assert!(matches!(status, ExitStatus::Exited(66)));
+    assert_eq!(stderr, b"");
+}
+
+#[test]
+fn failure_code() {
+    let (status, stderr) = run_in_child(|| {
+        ft::entry_point::set_failure_code(ExitCode::SOFTWARE);
+        call(|_, _| Err::<(), _>("internal error"))
+    });
+    assert!(matches!(status, ExitStatus::Exited(70)));
+    assert_eq!(stderr, b"prog: internal error\n");
+}
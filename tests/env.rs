@@ -0,0 +1,89 @@
+use ft::env::{Env, EnvBuilder};
+use ft::{charstar, CharStar};
+
+const ENTRIES: &[&CharStar] = &[
+    charstar!("HOME=/home/user"),
+    charstar!("PATH=/bin:/usr/bin"),
+    charstar!("EMPTY="),
+    charstar!("NOEQUAL"),
+];
+
+#[test]
+fn get() {
+    let env = Env::new(ENTRIES);
+    assert_eq!(env.get(b"PATH").unwrap(), "/bin:/usr/bin");
+    assert_eq!(env.get(b"EMPTY").unwrap(), "");
+    assert!(env.get(b"PAT").is_none());
+    assert!(env.get(b"NOEQUAL").is_none());
+}
+
+#[test]
+fn iter() {
+    let pairs: Vec<_> = Env::new(ENTRIES)
+        .iter()
+        .map(|(k, v)| (k.to_vec(), v.as_bytes().to_vec()))
+        .collect();
+
+    assert_eq!(
+        pairs,
+        [
+            (b"HOME".to_vec(), b"/home/user".to_vec()),
+            (b"PATH".to_vec(), b"/bin:/usr/bin".to_vec()),
+            (b"EMPTY".to_vec(), b"".to_vec()),
+            (b"NOEQUAL".to_vec(), b"".to_vec()),
+        ]
+    );
+}
+
+#[test]
+fn builder() {
+    let mut env = EnvBuilder::from_env(Env::new(ENTRIES)).unwrap();
+    env.set(b"HOME", b"/root").unwrap();
+    env.set(b"SHELL", b"/bin/sh").unwrap();
+    assert!(env.unset(b"PATH"));
+    assert!(!env.unset(b"PATH"));
+    assert!(env.set(b"A=B", b"C").is_err());
+
+    assert_eq!(env.get(b"HOME").unwrap(), "/root");
+    assert_eq!(env.get(b"SHELL").unwrap(), "/bin/sh");
+
+    let mut envp = env.as_envp();
+    let mut entries = Vec::new();
+    unsafe {
+        while !(*envp).is_null() {
+            entries.push((**envp).to_string());
+            envp = envp.add(1);
+        }
+    }
+
+    assert_eq!(
+        entries,
+        ["HOME=/root", "EMPTY=", "NOEQUAL", "SHELL=/bin/sh"]
+    );
+}
+
+#[test]
+fn empty_builder() {
+    let env = EnvBuilder::new();
+    assert!(unsafe { (*env.as_envp()).is_null() });
+}
+
+#[test]
+fn process_globals() {
+    let args: Vec<_> = ft::env::args().iter().map(|arg| arg.as_bytes()).collect();
+    let expected: Vec<_> = std::env::args_os().collect();
+    let expected: Vec<_> = expected.iter().map(|arg| arg.as_encoded_bytes()).collect();
+    assert_eq!(args, expected);
+    assert_eq!(ft::env::program_name().as_bytes(), expected[0]);
+
+    let mut vars: Vec<_> = ft::env::vars()
+        .iter()
+        .map(|(k, v)| (k.to_vec(), v.as_bytes().to_vec()))
+        .collect();
+    let mut expected: Vec<_> = std::env::vars_os()
+        .map(|(k, v)| (k.into_encoded_bytes(), v.into_encoded_bytes()))
+        .collect();
+    vars.sort();
+    expected.sort();
+    assert_eq!(vars, expected);
+}
@@ -0,0 +1,116 @@
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering::Relaxed;
+use std::time::Duration;
+
+use ft::fd::PipeFlags;
+use ft::process::{fork, ExitStatus, Fork, WaitOptions};
+use ft::{Errno, File};
+
+#[test]
+fn names() {
+    assert_eq!(Errno::NOENT.name(), "ENOENT");
+    assert_eq!(Errno::TOOBIG.name(), "E2BIG");
+    assert_eq!(Errno::WOULDBLOCK.name(), "EAGAIN");
+    assert_eq!(Errno::SUCCESS.name(), "SUCCESS");
+    assert_eq!(Errno::from_raw(-1).name(), "UNKNOWN");
+}
+
+#[test]
+fn from_name() {
+    assert_eq!(Errno::from_name("EADDRINUSE"), Some(Errno::ADDRINUSE));
+    assert_eq!(Errno::from_name("E2BIG"), Some(Errno::TOOBIG));
+    assert_eq!(Errno::from_name("ADDRINUSE"), None);
+
+    for raw in 0..200 {
+        let errno = Errno::from_raw(raw);
+        if errno.name() != "UNKNOWN" {
+            assert_eq!(Errno::from_name(errno.name()), Some(errno));
+        }
+    }
+}
+
+#[test]
+fn debug() {
+    assert_eq!(
+        format!("{:?}", Errno::NOENT),
+        "\"No such file or directory\" (ENOENT)"
+    );
+}
+
+#[test]
+fn description() {
+    assert_eq!(Errno::PIPE.description(), Some("Broken pipe"));
+    assert_eq!(Errno::from_raw(-1).description(), None);
+    assert_eq!(Errno::from_raw(-1).to_string(), "Unknown error -1");
+
+    assert_eq!(
+        Errno::OVERFLOW.to_string(),
+        "Value too large for defined data type"
+    );
+}
+
+#[test]
+fn restart_on_interrupt() {
+    assert!(ft::restart_on_interrupt());
+    assert!(ft::set_restart_on_interrupt(false));
+    assert!(!ft::restart_on_interrupt());
+    assert!(!ft::set_restart_on_interrupt(true));
+    assert!(ft::restart_on_interrupt());
+}
+
+static ALARMS: AtomicUsize = AtomicUsize::new(0);
+
+extern "C" fn on_alarm(_: libc::c_int) {
+    ALARMS.fetch_add(1, Relaxed);
+}
+
+/// Reads a pipe whose writer only sends data once `SIGALRM` interrupted the read.
+fn read_through_alarm() -> (ft::Result<()>, Vec<u8>) {
+    let (read, write) = File::pipe(PipeFlags::empty()).unwrap();
+
+    match fork().unwrap() {
+        Fork::Child => {
+            drop(read);
+            std::thread::sleep(Duration::from_millis(200));
+            let ok = write.write_all(b"hello").is_ok();
+            std::process::exit(if ok { 0 } else { 1 });
+        }
+        Fork::Parent(writer) => {
+            drop(write);
+            let mut timer: libc::itimerval = unsafe { core::mem::zeroed() };
+            timer.it_value.tv_usec = 50_000;
+            unsafe { libc::setitimer(libc::ITIMER_REAL, &timer, core::ptr::null_mut()) };
+            let mut data = Vec::new();
+            let res = read.read_to_vec(&mut data);
+            writer.wait(WaitOptions::empty()).unwrap();
+            (res, data)
+        }
+    }
+}
+
+#[test]
+fn interrupted_read() {
+    // The signal must be delivered to the reading thread, which is only guaranteed in a
+    // single-threaded child.
+    match fork().unwrap() {
+        Fork::Child => {
+            let mut action: libc::sigaction = unsafe { core::mem::zeroed() };
+            action.sa_sigaction = on_alarm as extern "C" fn(libc::c_int) as libc::sighandler_t;
+            unsafe { libc::sigaction(libc::SIGALRM, &action, core::ptr::null_mut()) };
+
+            ft::set_restart_on_interrupt(false);
+            let (res, _) = read_through_alarm();
+            let reported = res == Err(Errno::INTR) && ALARMS.load(Relaxed) == 1;
+
+            ft::set_restart_on_interrupt(true);
+            let (res, data) = read_through_alarm();
+            let restarted = res.is_ok() && data == b"hello" && ALARMS.load(Relaxed) == 2;
+
+            std::process::exit(if reported && restarted { 0 } else { 1 });
+        }
+        Fork::Parent(child) => {
+            let status = child.wait(WaitOptions::empty()).unwrap();
+            assert!(matches!(status, ExitStatus::Exited(0)));
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use ft::fd::OpenFlags;
+use ft::{charstar, Errno, Error, Fd, ResultExt};
+
+#[test]
+fn display() {
+    let path = charstar!("foo.conf");
+    let err = Fd::open(path, OpenFlags::READ_ONLY)
+        .path_context("open", path)
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "open \"foo.conf\": No such file or directory"
+    );
+
+    let err = Error::new(Errno::PIPE, "write");
+    assert_eq!(err.to_string(), "write: Broken pipe");
+
+    let err = Error::from(Errno::PIPE);
+    assert_eq!(err.to_string(), "Broken pipe");
+}
+
+#[test]
+fn into_owned() {
+    let err = {
+        let path = charstar!("a.txt").to_owned();
+        Error::new(Errno::NOENT, "stat")
+            .with_path(&path)
+            .into_owned()
+    };
+
+    assert_eq!(err.path().unwrap(), "a.txt");
+    assert_eq!(Errno::from(err), Errno::NOENT);
+}
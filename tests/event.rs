@@ -0,0 +1,102 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use ft::fd::{
+    poll, EventFd, EventFdFlags, PollFd, PollFlags, SignalFd, SignalFdFlags, TimerFd, TimerFdFlags,
+};
+use ft::process::{fork, ExitStatus, Fork, WaitOptions};
+use ft::{Clock, Errno, Signal};
+
+#[test]
+fn event_fd() {
+    let event = EventFd::new(0, EventFdFlags::NON_BLOCKING).unwrap();
+    assert_eq!(event.consume().unwrap_err(), Errno::WOULDBLOCK);
+
+    event.notify(2).unwrap();
+    event.notify(3).unwrap();
+    let mut fds = [PollFd::new(*event, PollFlags::IN)];
+    assert_eq!(poll(&mut fds, Some(Duration::ZERO)), Ok(1));
+    assert_eq!(event.consume(), Ok(5));
+
+    let semaphore = EventFd::new(2, EventFdFlags::SEMAPHORE | EventFdFlags::NON_BLOCKING).unwrap();
+    assert_eq!(semaphore.consume(), Ok(1));
+    assert_eq!(semaphore.consume(), Ok(1));
+    assert_eq!(semaphore.consume().unwrap_err(), Errno::WOULDBLOCK);
+}
+
+#[test]
+fn timer_fd() {
+    let timer = TimerFd::new(Clock::MONOTONIC, TimerFdFlags::CLOEXEC).unwrap();
+    assert_eq!(timer.remaining(), Ok(None));
+
+    timer.set_after(Duration::from_millis(5)).unwrap();
+    assert!(timer.remaining().unwrap().is_some());
+    assert_eq!(timer.wait(), Ok(1));
+
+    timer.set_at(Clock::MONOTONIC.get()).unwrap();
+    assert_eq!(timer.wait(), Ok(1));
+
+    timer
+        .set_interval(Duration::from_millis(1), Duration::from_millis(1))
+        .unwrap();
+    std::thread::sleep(Duration::from_millis(20));
+    assert!(timer.wait().unwrap() >= 2);
+
+    timer.disarm().unwrap();
+    assert_eq!(timer.remaining(), Ok(None));
+}
+
+#[test]
+fn timer_fd_async() {
+    let timer = TimerFd::new(Clock::MONOTONIC, TimerFdFlags::NON_BLOCKING).unwrap();
+    timer.set_after(Duration::from_millis(5)).unwrap();
+
+    let expirations = Rc::new(Cell::new(0));
+    let result = expirations.clone();
+    ft::runtime::spawn(async move {
+        result.set(timer.async_wait().await.unwrap());
+    });
+
+    while ft::runtime::run_until_idle().unwrap() != 0 {}
+    assert_eq!(expirations.get(), 1);
+}
+
+#[test]
+fn signal_fd() {
+    let signals = SignalFd::new(&[Signal::USR1], SignalFdFlags::NON_BLOCKING).unwrap();
+    assert_eq!(signals.read().unwrap_err(), Errno::WOULDBLOCK);
+
+    Signal::USR1.raise();
+    let info = signals.read().unwrap();
+    assert_eq!(info.signal(), Signal::USR1);
+    assert_eq!(info.pid(), ft::Pid::from_raw(std::process::id() as i32));
+}
+
+#[test]
+fn signal_fd_failure_restores_mask() {
+    // The resource limit is only lowered in a child, to leave the test harness alone.
+    match fork().unwrap() {
+        Fork::Child => {
+            let mut limit = unsafe { std::mem::zeroed() };
+            unsafe { libc::getrlimit(libc::RLIMIT_NOFILE, &mut limit) };
+            limit.rlim_cur = 0;
+            unsafe { libc::setrlimit(libc::RLIMIT_NOFILE, &limit) };
+            let failed = SignalFd::new(&[Signal::USR2], SignalFdFlags::empty()).err();
+
+            let mut mask = unsafe { std::mem::zeroed() };
+            unsafe { libc::pthread_sigmask(libc::SIG_BLOCK, std::ptr::null(), &mut mask) };
+            let blocked = unsafe { libc::sigismember(&mask, libc::SIGUSR2) } == 1;
+
+            std::process::exit(if failed == Some(Errno::MFILE) && !blocked {
+                0
+            } else {
+                1
+            });
+        }
+        Fork::Parent(child) => {
+            let status = child.wait(WaitOptions::empty()).unwrap();
+            assert!(matches!(status, ExitStatus::Exited(0)));
+        }
+    }
+}
@@ -0,0 +1,200 @@
+use core::mem::MaybeUninit;
+
+use ft::fd::{IoSlice, IoSliceMut, LockKind, OpenFlags, PipeFlags, SeekFrom};
+use ft::process::{fork, ExitStatus, Fork, WaitOptions};
+use ft::{Errno, File};
+
+mod common;
+
+use common::{create_file, temp_file, temp_path};
+
+#[test]
+fn pipe() {
+    let (read, write) = File::pipe(PipeFlags::CLOEXEC).unwrap();
+    write.write_all(b"hello").unwrap();
+    drop(write);
+
+    let mut buf = Vec::new();
+    read.read_to_vec(&mut buf).unwrap();
+    assert_eq!(buf, b"hello");
+}
+
+#[test]
+fn pipe_non_blocking() {
+    let (read, _write) = File::pipe(PipeFlags::NON_BLOCKING).unwrap();
+    let mut buf = [MaybeUninit::uninit(); 4];
+    assert_eq!(read.read(&mut buf), Err(Errno::WOULDBLOCK));
+}
+
+#[test]
+fn duplicate() {
+    let (read, write) = File::pipe(PipeFlags::CLOEXEC).unwrap();
+    let copy = File::from_fd(write.duplicate().unwrap());
+    drop(write);
+    assert!(!copy.is_cloexec().unwrap());
+    assert_eq!(copy.duplicate_to(*copy, true), Ok(*copy));
+    assert!(copy.is_cloexec().unwrap());
+    copy.write_all(b"abc").unwrap();
+    drop(copy);
+
+    let mut buf = Vec::new();
+    read.read_to_vec(&mut buf).unwrap();
+    assert_eq!(buf, b"abc");
+}
+
+#[test]
+fn redirect() {
+    let (read, write) = File::pipe(PipeFlags::CLOEXEC).unwrap();
+    let (other, _other_write) = File::pipe(PipeFlags::CLOEXEC).unwrap();
+    let target = File::from_fd(other.duplicate_cloexec().unwrap());
+
+    {
+        let guard = target.redirect(*write).unwrap();
+        assert_eq!(guard.target(), *target);
+        assert!(!target.is_cloexec().unwrap());
+        target.write_all(b"redirected").unwrap();
+    }
+
+    drop(write);
+    let mut buf = Vec::new();
+    read.read_to_vec(&mut buf).unwrap();
+    assert_eq!(buf, b"redirected");
+
+    // `target` refers to the read end of the other pipe again.
+    assert!(target.write(b"x").is_err());
+    assert!(target.is_cloexec().unwrap());
+}
+
+#[test]
+fn seek_and_positional() {
+    let (_path, file) = temp_file("seek", b"hello world");
+    assert_eq!(file.seek(SeekFrom::Current(0)).unwrap(), 11);
+    assert_eq!(file.seek(SeekFrom::End(-5)).unwrap(), 6);
+    assert_eq!(file.seek(SeekFrom::Start(0)).unwrap(), 0);
+
+    assert_eq!(file.write_at(b"W", 6).unwrap(), 1);
+    let mut buf = [MaybeUninit::uninit(); 5];
+    assert_eq!(file.read_at(&mut buf, 6).unwrap(), 5);
+    assert_eq!(buf.map(|b| unsafe { b.assume_init() }), *b"World");
+    assert_eq!(file.seek(SeekFrom::Current(0)).unwrap(), 0);
+
+    file.truncate(5).unwrap();
+    assert_eq!(file.metadata().unwrap().size(), 5);
+    assert_eq!(file.read_at(&mut buf, 5).unwrap(), 0);
+}
+
+#[test]
+fn advance_slices() {
+    let mut storage = [
+        IoSlice::new(b""),
+        IoSlice::new(b"abc"),
+        IoSlice::new(b"de"),
+        IoSlice::new(b"fgh"),
+    ];
+    let mut bufs = &mut storage[..];
+
+    IoSlice::advance_slices(&mut bufs, 4);
+    assert_eq!(bufs.len(), 2);
+    assert_eq!(&*bufs[0], b"e");
+
+    IoSlice::advance_slices(&mut bufs, 4);
+    assert!(bufs.is_empty());
+}
+
+#[test]
+fn vectored() {
+    let (read, write) = File::pipe(PipeFlags::CLOEXEC).unwrap();
+    let mut bufs = [
+        IoSlice::new(b"HTTP/1.1 200 OK\r\n\r\n"),
+        IoSlice::new(b""),
+        IoSlice::new(b"body"),
+    ];
+    write.write_all_vectored(&mut bufs).unwrap();
+    drop(write);
+
+    let mut head = [MaybeUninit::uninit(); 8];
+    let mut tail = [MaybeUninit::uninit(); 32];
+    let mut bufs = [IoSliceMut::new(&mut head), IoSliceMut::new(&mut tail)];
+    assert_eq!(read.read_vectored(&mut bufs).unwrap(), 23);
+
+    assert_eq!(head.map(|b| unsafe { b.assume_init() }), *b"HTTP/1.1");
+    let tail: Vec<u8> = tail[..15]
+        .iter()
+        .map(|b| unsafe { b.assume_init() })
+        .collect();
+    assert_eq!(tail, b" 200 OK\r\n\r\nbody");
+}
+
+#[test]
+fn many_slices() {
+    let (read, write) = File::pipe(PipeFlags::CLOEXEC).unwrap();
+    let data: Vec<u8> = (0..3000).map(|i| i as u8).collect();
+    let mut bufs: Vec<IoSlice> = data.chunks(1).map(IoSlice::new).collect();
+    write.write_all_vectored(&mut bufs).unwrap();
+    drop(write);
+
+    let mut received = Vec::new();
+    read.read_to_vec(&mut received).unwrap();
+    assert_eq!(received, data);
+}
+
+#[test]
+fn descriptor_flags() {
+    let (read, _write) = File::pipe(PipeFlags::empty()).unwrap();
+    assert!(!read.is_cloexec().unwrap());
+    read.set_cloexec(true).unwrap();
+    assert!(read.is_cloexec().unwrap());
+
+    let copy = File::from_fd(read.duplicate_cloexec().unwrap());
+    assert!(copy.is_cloexec().unwrap());
+    copy.set_cloexec(false).unwrap();
+    assert!(!copy.is_cloexec().unwrap());
+
+    let path = temp_path("append-flags");
+    let file = create_file(&path, OpenFlags::WRITE_ONLY | OpenFlags::APPEND);
+    file.set_nonblocking(true).unwrap();
+    let flags = file.get_flags().unwrap();
+    assert!(flags.contains(OpenFlags::APPEND | OpenFlags::NON_BLOCKING));
+    file.set_nonblocking(false).unwrap();
+    let flags = file.get_flags().unwrap();
+    assert!(flags.contains(OpenFlags::APPEND));
+    assert!(!flags.contains(OpenFlags::NON_BLOCKING));
+}
+
+#[test]
+fn record_locks() {
+    let (_path, file) = temp_file("lock", b"");
+
+    let guard = file.try_lock(LockKind::Write, 0, 0).unwrap();
+    let parent = ft::Pid::from_raw(std::process::id() as i32);
+
+    // Record locks belong to processes, so conflicts can only be observed from a child.
+    match fork().unwrap() {
+        Fork::Child => {
+            let conflict = matches!(
+                file.try_lock(LockKind::Read, 0, 1),
+                Err(Errno::AGAIN | Errno::ACCES)
+            );
+            let holder = file.lock_holder(LockKind::Read, 0, 1) == Ok(Some(parent));
+            std::process::exit(if conflict && holder { 0 } else { 1 });
+        }
+        Fork::Parent(child) => {
+            let status = child.wait(WaitOptions::empty()).unwrap();
+            assert!(matches!(status, ExitStatus::Exited(0)));
+        }
+    }
+
+    assert_eq!(file.lock_holder(LockKind::Write, 0, 0), Ok(None));
+    guard.unlock().unwrap();
+
+    match fork().unwrap() {
+        Fork::Child => {
+            let ok = file.try_lock(LockKind::Write, 0, 0).is_ok();
+            std::process::exit(if ok { 0 } else { 1 });
+        }
+        Fork::Parent(child) => {
+            let status = child.wait(WaitOptions::empty()).unwrap();
+            assert!(matches!(status, ExitStatus::Exited(0)));
+        }
+    }
+}
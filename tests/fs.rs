@@ -0,0 +1,170 @@
+use ft::charstar::CharStarBox;
+use ft::fd::{Mode, OpenFlags, PipeFlags};
+use ft::fs::{self, AccessFlags, FileType};
+use ft::{charstar, Errno, Fd, File};
+
+mod common;
+
+use common::{create_file, temp_path};
+
+#[test]
+fn metadata() {
+    let meta = fs::metadata(charstar!("/")).unwrap();
+    assert!(meta.is_dir());
+    assert_eq!(meta.file_type(), FileType::Directory);
+    assert!(meta.mode().contains(Mode::OWNER_READ));
+    assert_eq!(meta.uid(), ft::Uid::ROOT);
+
+    let file = File::open(charstar!("Cargo.toml")).unwrap();
+    let meta = file.metadata().unwrap();
+    assert!(meta.is_file());
+    assert_eq!(
+        meta.size(),
+        fs::metadata(charstar!("Cargo.toml")).unwrap().size()
+    );
+    assert!(meta.modified() > ft::Instant::EPOCH);
+
+    let (read, _write) = File::pipe(PipeFlags::CLOEXEC).unwrap();
+    assert_eq!(read.metadata().unwrap().file_type(), FileType::Fifo);
+
+    assert_eq!(
+        fs::metadata(charstar!("does/not/exist")).unwrap_err(),
+        Errno::NOENT
+    );
+}
+
+#[test]
+fn symlink_metadata() {
+    let meta = fs::symlink_metadata(charstar!("/proc/self")).unwrap();
+    assert!(meta.is_symlink());
+    assert!(fs::metadata(charstar!("/proc/self")).unwrap().is_dir());
+}
+
+#[test]
+fn dir() {
+    let mut dir = fs::Dir::open(charstar!("tests")).unwrap();
+    let mut found = false;
+
+    while let Some(entry) = dir.read().unwrap() {
+        assert_ne!(entry.name(), ".");
+        assert_ne!(entry.name(), "..");
+
+        if entry.name() == "fs.rs" {
+            assert!(matches!(
+                entry.file_type(),
+                FileType::Regular | FileType::Unknown
+            ));
+            assert_eq!(
+                entry.inode(),
+                fs::metadata(charstar!("tests/fs.rs")).unwrap().inode()
+            );
+            found = true;
+        }
+    }
+
+    assert!(found);
+    assert!(dir.read().unwrap().is_none());
+
+    let names: Vec<_> = fs::Dir::open(charstar!("tests/common"))
+        .unwrap()
+        .map(|entry| entry.unwrap().into_name())
+        .collect();
+    assert_eq!(names.len(), 1);
+    assert_eq!(&*names[0], "mod.rs");
+    assert_eq!(
+        fs::Dir::open(charstar!("Cargo.toml")).unwrap_err(),
+        Errno::NOTDIR
+    );
+}
+
+#[test]
+fn mutations() {
+    let dir = temp_path("mutations");
+    let _ = fs::rmdir(&dir);
+
+    fs::mkdir(
+        &dir,
+        Mode::OWNER_READ | Mode::OWNER_WRITE | Mode::OWNER_EXECUTE,
+    )
+    .unwrap();
+    assert_eq!(fs::mkdir(&dir, Mode::empty()).unwrap_err(), Errno::EXIST);
+    let handle = fs::Dir::open(&dir).unwrap();
+    let dirfd = handle.fd();
+
+    drop(create_file(&dir.join("a"), OpenFlags::WRITE_ONLY));
+
+    fs::rename_at(dirfd, charstar!("a"), dirfd, charstar!("b")).unwrap();
+    fs::access_at(dirfd, charstar!("b"), AccessFlags::empty()).unwrap();
+    assert_eq!(
+        fs::access_at(dirfd, charstar!("a"), AccessFlags::empty()).unwrap_err(),
+        Errno::NOENT
+    );
+
+    fs::chmod_at(dirfd, charstar!("b"), Mode::OWNER_READ).unwrap();
+    let meta = fs::metadata(&dir.join("b")).unwrap();
+    assert_eq!(meta.mode(), Mode::OWNER_READ);
+
+    fs::symlink_at(charstar!("b"), dirfd, charstar!("link")).unwrap();
+    assert_eq!(&*fs::readlink_at(dirfd, charstar!("link")).unwrap(), "b");
+
+    fs::mkdir_at(dirfd, charstar!("sub"), Mode::OWNER_EXECUTE).unwrap();
+    fs::rmdir_at(dirfd, charstar!("sub")).unwrap();
+    fs::unlink_at(dirfd, charstar!("link")).unwrap();
+    fs::unlink_at(dirfd, charstar!("b")).unwrap();
+    fs::rmdir(&dir).unwrap();
+}
+
+#[test]
+fn getcwd() {
+    let cwd = fs::getcwd().unwrap();
+    let expected = std::env::current_dir().unwrap();
+    assert_eq!(cwd.as_bytes(), expected.as_os_str().as_encoded_bytes());
+}
+
+#[test]
+fn open_options() {
+    use fs::OpenOptions;
+
+    let path = temp_path("open-options");
+    let _ = fs::unlink(&path);
+
+    let lock = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(Mode::OWNER_READ | Mode::OWNER_WRITE)
+        .custom_flags(OpenFlags::CLOEXEC)
+        .open(&path)
+        .unwrap();
+    lock.write_all(b"a").unwrap();
+    assert_eq!(
+        OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+            .err(),
+        Some(Errno::EXIST)
+    );
+    assert_eq!(
+        OpenOptions::new().read(true).create(true).open(&path).err(),
+        Some(Errno::INVAL)
+    );
+
+    let appended = OpenOptions::new().append(true).open(&path).unwrap();
+    appended.write_all(b"b").unwrap();
+
+    let tmp = Fd::open(
+        charstar!("/tmp"),
+        OpenFlags::READ_ONLY | OpenFlags::DIRECTORY,
+    )
+    .unwrap();
+    let name = path.as_bytes().strip_prefix(b"/tmp/").unwrap();
+    let name = CharStarBox::from_bytes(name).unwrap();
+    let file = OpenOptions::new().read(true).open_at(tmp, &name).unwrap();
+    let mut contents = Vec::new();
+    file.read_to_vec(&mut contents).unwrap();
+    assert_eq!(contents, b"ab");
+    tmp.close().unwrap();
+
+    fs::chmod(&path, Mode::OWNER_READ | Mode::SET_UID).unwrap();
+    assert!(fs::metadata(&path).unwrap().mode().contains(Mode::SET_UID));
+}
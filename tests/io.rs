@@ -0,0 +1,126 @@
+use std::cell::RefCell;
+use std::mem::MaybeUninit;
+use std::rc::Rc;
+
+use ft::collections::ReadBuffer;
+use ft::fd::PipeFlags;
+use ft::io::{Read, Write};
+use ft::{Errno, File};
+
+#[test]
+fn slices() {
+    let mut input: &[u8] = b"hello";
+    let mut buf = [MaybeUninit::uninit(); 3];
+    assert_eq!(input.read(&mut buf), Ok(3));
+    assert_eq!(input, b"lo");
+
+    let mut rest = Vec::new();
+    input.read_to_vec(&mut rest).unwrap();
+    assert_eq!(rest, b"lo");
+    assert_eq!(input, b"");
+
+    let mut storage = [0u8; 4];
+    let mut output: &mut [u8] = &mut storage;
+    output.write_all(b"abc").unwrap();
+    assert_eq!(output.write_all(b"de"), Err(Errno::NOSPC));
+    assert_eq!(storage, *b"abcd");
+}
+
+#[test]
+fn vec_write_fmt() {
+    let mut out = Vec::new();
+    write!(out, "{}-{}", 1, 2 * 3).unwrap();
+    out.write_all(b"!").unwrap();
+    assert_eq!(out, b"1-6!");
+}
+
+#[test]
+fn read_buffer_from_slice() {
+    let mut input: &[u8] = b"GET / HTTP/1.1\r\nHost: x\r\n\r\nbody";
+    let mut buf = ReadBuffer::new();
+
+    assert_eq!(
+        buf.read_until(&mut input, b"\r\n").unwrap(),
+        b"GET / HTTP/1.1\r\n"
+    );
+    assert_eq!(buf.read_until(&mut input, b"\r\n").unwrap(), b"Host: x\r\n");
+    assert_eq!(buf.read_until(&mut input, b"\r\n").unwrap(), b"\r\n");
+    assert_eq!(buf.read_exact(&mut input, 2).unwrap(), b"bo");
+
+    // The reader is exhausted before enough bytes are available.
+    assert_eq!(buf.read_exact(&mut input, 3), Err(Errno::NODATA));
+    assert_eq!(buf.pending(), b"dy");
+
+    // The reader is exhausted before the delimiter is found.
+    assert_eq!(buf.read_until(&mut input, b"\r\n").unwrap(), b"dy");
+    assert_eq!(buf.read_until(&mut input, b"\r\n").unwrap(), b"");
+}
+
+#[test]
+fn file_as_reader_and_writer() {
+    let (read, write) = File::pipe(PipeFlags::empty()).unwrap();
+
+    fn send(mut writer: impl Write) {
+        writer.write_all(b"through a pipe\n").unwrap();
+    }
+
+    send(write);
+
+    let mut buf = ReadBuffer::new();
+    assert_eq!(buf.read_until(*read, b"\n").unwrap(), b"through a pipe\n");
+}
+
+#[test]
+fn read_buffer_futures_at_eof() {
+    let (read, mut write) = File::pipe(PipeFlags::NON_BLOCKING).unwrap();
+    write.write_all(b"abcd\nef").unwrap();
+    drop(write);
+
+    let results = Rc::new(RefCell::new(Vec::new()));
+    let out = results.clone();
+    ft::runtime::spawn(async move {
+        let mut buf = ReadBuffer::new();
+        let line = buf.async_read_until(*read, b"\n").await.map(|s| s.to_vec());
+        out.borrow_mut().push(line);
+        // Exactly `count` bytes are pending, so the reader is not used.
+        let exact = buf.async_read_exact(*read, 2).await.map(|s| s.to_vec());
+        out.borrow_mut().push(exact);
+        let exact = buf.async_read_exact(*read, 1).await.map(|s| s.to_vec());
+        out.borrow_mut().push(exact);
+        let line = buf.async_read_until(*read, b"\n").await.map(|s| s.to_vec());
+        out.borrow_mut().push(line);
+    });
+
+    while ft::runtime::run_until_idle().unwrap() != 0 {}
+    assert_eq!(
+        *results.borrow(),
+        [
+            Ok(b"abcd\n".to_vec()),
+            Ok(b"ef".to_vec()),
+            Err(Errno::NODATA),
+            Ok(Vec::new()),
+        ]
+    );
+}
+
+#[test]
+fn read_to_vec_future() {
+    let (read, mut write) = File::pipe(PipeFlags::NON_BLOCKING).unwrap();
+    write.write_all(b"first").unwrap();
+
+    let result = Rc::new(RefCell::new(Vec::new()));
+    let out = result.clone();
+    ft::runtime::spawn(async move {
+        let mut vec = Vec::new();
+        read.async_read_to_vec(&mut vec).await.unwrap();
+        *out.borrow_mut() = vec;
+    });
+
+    // The first chunk is read, then the future waits for more data.
+    assert_eq!(ft::runtime::run_until_idle().unwrap(), 1);
+    write.write_all(b", second").unwrap();
+    drop(write);
+
+    while ft::runtime::run_until_idle().unwrap() != 0 {}
+    assert_eq!(*result.borrow(), b"first, second");
+}
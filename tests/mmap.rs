@@ -0,0 +1,65 @@
+use ft::fd::SeekFrom;
+use ft::mmap::{Advice, Mmap, MmapMut, Prot};
+
+mod common;
+
+use common::temp_file;
+
+#[test]
+fn anonymous() {
+    let mut map = MmapMut::anonymous(4096).unwrap();
+    assert_eq!(map.len(), 4096);
+    assert!(map.iter().all(|&b| b == 0));
+
+    map[..5].copy_from_slice(b"hello");
+    map.advise(Advice::Sequential).unwrap();
+    let map = map.make_read_only().unwrap();
+    assert_eq!(&map[..5], b"hello");
+
+    let mut map = map.make_mut().unwrap();
+    unsafe {
+        map.protect(Prot::empty()).unwrap();
+        map.protect(Prot::READ | Prot::WRITE).unwrap();
+    }
+    map[4095] = 1;
+    assert_eq!(map[4095], 1);
+}
+
+#[test]
+fn remap() {
+    let mut map = MmapMut::anonymous(4096).unwrap();
+    map[..3].copy_from_slice(b"abc");
+
+    map.remap(3 * 4096).unwrap();
+    assert_eq!(map.len(), 3 * 4096);
+    assert_eq!(&map[..3], b"abc");
+    assert!(map[4096..].iter().all(|&b| b == 0));
+    map[3 * 4096 - 1] = 2;
+}
+
+#[test]
+fn map_file() {
+    let (_path, file) = temp_file("mmap-read", b"\x7fELF and more");
+    let map = unsafe { Mmap::map_file(*file).unwrap() };
+    drop(file);
+    assert_eq!(&*map, b"\x7fELF and more");
+
+    let (_path, file) = temp_file("mmap-empty", b"");
+    let map = unsafe { Mmap::map_file(*file).unwrap() };
+    assert!(map.is_empty());
+    assert_eq!(&*map, b"");
+}
+
+#[test]
+fn map_file_shared() {
+    let (_path, file) = temp_file("mmap-shared", b"hello, world");
+    let mut map = unsafe { MmapMut::map_file(*file).unwrap() };
+    map[7..].copy_from_slice(b"there");
+    map.flush().unwrap();
+    drop(map);
+
+    file.seek(SeekFrom::Start(0)).unwrap();
+    let mut contents = Vec::new();
+    file.read_to_vec(&mut contents).unwrap();
+    assert_eq!(contents, b"hello, there");
+}
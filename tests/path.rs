@@ -0,0 +1,79 @@
+use ft::fs::{Component, PathBuf};
+use ft::Errno;
+
+fn path(s: &str) -> PathBuf {
+    PathBuf::from_bytes(s.as_bytes()).unwrap()
+}
+
+#[test]
+fn components() {
+    let p = path("/usr//lib/./x/../");
+    let components: Vec<_> = p.components().collect();
+    assert_eq!(
+        components,
+        [
+            Component::RootDir,
+            Component::Normal(b"usr"),
+            Component::Normal(b"lib"),
+            Component::Normal(b"x"),
+            Component::ParentDir,
+        ]
+    );
+
+    let p = path("./a");
+    let components: Vec<_> = p.components().collect();
+    assert_eq!(components, [Component::CurDir, Component::Normal(b"a")]);
+}
+
+#[test]
+fn accessors() {
+    let p = path("/var/www/index.html");
+    assert_eq!(p.parent(), Some(&b"/var/www"[..]));
+    assert_eq!(p.file_name(), Some(&b"index.html"[..]));
+    assert_eq!(p.extension(), Some(&b"html"[..]));
+
+    assert_eq!(path("/a").parent(), Some(&b"/"[..]));
+    assert_eq!(path("/").parent(), None);
+    assert_eq!(path("a").parent(), Some(&b""[..]));
+    assert_eq!(path("a/..").file_name(), None);
+    assert_eq!(path("a/.").file_name(), Some(&b"a"[..]));
+    assert_eq!(path(".").file_name(), None);
+    assert_eq!(path(".bashrc").extension(), None);
+    assert_eq!(path("archive.tar.gz").extension(), Some(&b"gz"[..]));
+}
+
+#[test]
+fn push_pop() {
+    let mut p = path("/var");
+    p.push(b"www").unwrap();
+    assert_eq!(&*p, "/var/www");
+    p.push(b"/etc").unwrap();
+    assert_eq!(&*p, "/etc");
+    assert!(p.pop().unwrap());
+    assert_eq!(&*p, "/");
+    assert!(!p.pop().unwrap());
+}
+
+#[test]
+fn normalize() {
+    assert_eq!(&*path("/a/./b/../c//").normalize().unwrap(), "/a/c");
+    assert_eq!(&*path("a/..").normalize().unwrap(), ".");
+    assert_eq!(path("/..").normalize().unwrap_err(), Errno::ACCES);
+    assert_eq!(path("a/../..").normalize().unwrap_err(), Errno::ACCES);
+}
+
+#[test]
+fn push_within() {
+    let mut root = path("/var/www");
+    root.push_within(b"/docs/../index.html").unwrap();
+    assert_eq!(&*root, "/var/www/index.html");
+
+    let mut root = path("/var/www");
+    assert_eq!(root.push_within(b"/../etc/passwd"), Err(Errno::ACCES));
+    assert_eq!(root.push_within(b"a/../../www2"), Err(Errno::ACCES));
+    assert_eq!(&*root, "/var/www");
+
+    let mut root = path("/");
+    root.push_within(b"x").unwrap();
+    assert_eq!(&*root, "/x");
+}
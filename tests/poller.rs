@@ -0,0 +1,108 @@
+use core::time::Duration;
+use std::time::Instant;
+
+use ft::fd::{Events, Interest, PipeFlags, PollFd, PollFlags, Poller, Readiness};
+use ft::{Errno, File};
+
+#[test]
+fn readiness() {
+    let (read, write) = File::pipe(PipeFlags::CLOEXEC).unwrap();
+    let mut poller = Poller::new().unwrap();
+    let mut events = Events::with_capacity(8).unwrap();
+
+    poller.add(*read, Interest::READABLE, 7).unwrap();
+    assert_eq!(
+        poller.add(*read, Interest::READABLE, 7).unwrap_err(),
+        Errno::EXIST
+    );
+    assert_eq!(poller.wait(&mut events, Some(Duration::ZERO)), Ok(0));
+
+    write.write_all(b"x").unwrap();
+    assert_eq!(poller.wait(&mut events, None), Ok(1));
+    let event = events.iter().next().unwrap();
+    assert_eq!(event.token, 7);
+    assert!(event.readiness.contains(Readiness::READABLE));
+
+    poller.modify(*read, Interest::empty(), 8).unwrap();
+    poller.add(*write, Interest::WRITABLE, 9).unwrap();
+    assert_eq!(poller.wait(&mut events, None), Ok(1));
+    assert_eq!(events.iter().next().unwrap().token, 9);
+
+    poller.remove(*write).unwrap();
+    assert_eq!(poller.remove(*write).unwrap_err(), Errno::NOENT);
+
+    drop(write);
+    poller.modify(*read, Interest::READABLE, 8).unwrap();
+    assert_eq!(poller.wait(&mut events, None), Ok(1));
+    assert!(events
+        .iter()
+        .next()
+        .unwrap()
+        .readiness
+        .contains(Readiness::HANGUP));
+}
+
+// The `poll` fallback reports edge-triggered registrations like level-triggered ones.
+#[test]
+#[cfg(all(target_os = "linux", not(feature = "restrict-functions")))]
+fn edge_triggered() {
+    let (read, write) = File::pipe(PipeFlags::CLOEXEC).unwrap();
+    let mut poller = Poller::new().unwrap();
+    let mut events = Events::with_capacity(1).unwrap();
+
+    poller
+        .add(*read, Interest::READABLE | Interest::EDGE_TRIGGERED, 1)
+        .unwrap();
+    write.write_all(b"x").unwrap();
+    assert_eq!(poller.wait(&mut events, None), Ok(1));
+
+    // The data has not been read, but no new data arrived.
+    assert_eq!(poller.wait(&mut events, Some(Duration::ZERO)), Ok(0));
+
+    write.write_all(b"y").unwrap();
+    assert_eq!(poller.wait(&mut events, Some(Duration::ZERO)), Ok(1));
+}
+
+#[test]
+fn small_buffer() {
+    let pipes = [
+        File::pipe(PipeFlags::CLOEXEC).unwrap(),
+        File::pipe(PipeFlags::CLOEXEC).unwrap(),
+    ];
+    let mut poller = Poller::new().unwrap();
+    let mut events = Events::with_capacity(1).unwrap();
+
+    for (token, (read, write)) in pipes.iter().enumerate() {
+        poller
+            .add(**read, Interest::READABLE, token as u64)
+            .unwrap();
+        write.write_all(b"x").unwrap();
+    }
+
+    let mut seen = [false; 2];
+    for _ in 0..2 {
+        assert_eq!(poller.wait(&mut events, None), Ok(1));
+        assert_eq!(events.len(), 1);
+        seen[events.iter().next().unwrap().token as usize] = true;
+    }
+    assert_eq!(seen, [true, true]);
+}
+
+#[test]
+fn sub_millisecond_timeout() {
+    let (read, _write) = File::pipe(PipeFlags::CLOEXEC).unwrap();
+    let mut poller = Poller::new().unwrap();
+    let mut events = Events::with_capacity(1).unwrap();
+    poller.add(*read, Interest::READABLE, 0).unwrap();
+
+    // The timeout is rounded up instead of turning into a non-blocking call.
+    let timeout = Duration::from_micros(500);
+    let start = Instant::now();
+    assert_eq!(poller.wait(&mut events, Some(timeout)), Ok(0));
+    assert!(start.elapsed() >= timeout);
+
+    let start = Instant::now();
+    let mut fds = [PollFd::new(*read, PollFlags::IN)];
+    assert_eq!(ft::fd::poll(&mut fds, Some(timeout)), Ok(0));
+    assert!(start.elapsed() >= timeout);
+}
@@ -0,0 +1,103 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use ft::fd::{PipeFlags, SeekFrom, SpliceFlags};
+use ft::{Fd, File};
+
+mod common;
+
+use common::temp_file;
+
+/// Reads the whole contents of `fd`, starting at the start of the file.
+fn contents(fd: Fd) -> Vec<u8> {
+    fd.seek(SeekFrom::Start(0)).unwrap();
+    let mut vec = Vec::new();
+    fd.read_to_vec(&mut vec).unwrap();
+    vec
+}
+
+#[test]
+fn send_file() {
+    let (_path, file) = temp_file("send-file", b"hello, world");
+    let (read, write) = File::pipe(PipeFlags::CLOEXEC).unwrap();
+
+    let mut offset = 7;
+    assert_eq!(file.send_file_to(*write, Some(&mut offset), 100), Ok(5));
+    assert_eq!(offset, 12);
+    assert_eq!(file.send_file_to(*write, Some(&mut offset), 100), Ok(0));
+    drop(write);
+
+    let mut received = Vec::new();
+    read.read_to_vec(&mut received).unwrap();
+    assert_eq!(received, b"world");
+}
+
+#[test]
+fn splice_and_tee() {
+    let (_path, file) = temp_file("splice", b"");
+    let (read, write) = File::pipe(PipeFlags::CLOEXEC).unwrap();
+    let (copy_read, copy_write) = File::pipe(PipeFlags::CLOEXEC).unwrap();
+
+    write.write_all(b"spliced").unwrap();
+    assert_eq!(read.tee(*copy_write, 100, SpliceFlags::empty()), Ok(7));
+
+    let mut offset = 2;
+    assert_eq!(
+        read.splice(None, *file, Some(&mut offset), 100, SpliceFlags::MOVE),
+        Ok(7)
+    );
+    assert_eq!(offset, 9);
+    assert_eq!(contents(*file), b"\0\0spliced");
+
+    drop(copy_write);
+    let mut copy = Vec::new();
+    copy_read.read_to_vec(&mut copy).unwrap();
+    assert_eq!(copy, b"spliced");
+}
+
+#[test]
+fn copy_file_range() {
+    let (_src_path, src) = temp_file("copy-src", b"0123456789");
+    let (_dst_path, dst) = temp_file("copy-dst", b"");
+
+    let mut offset_in = 4;
+    let mut copied = 0;
+    while copied < 6 {
+        match src
+            .copy_file_range(Some(&mut offset_in), *dst, None, 6 - copied)
+            .unwrap()
+        {
+            0 => break,
+            count => copied += count,
+        }
+    }
+    assert_eq!(offset_in, 10);
+    assert_eq!(contents(*dst), b"456789");
+}
+
+#[test]
+fn async_send_file() {
+    let data: Vec<u8> = (0..300_000u32).map(|i| i as u8).collect();
+    let (_path, file) = temp_file("async-send-file", &data);
+    let (read, write) = File::pipe(PipeFlags::CLOEXEC | PipeFlags::NON_BLOCKING).unwrap();
+
+    let sent = Rc::new(RefCell::new(None));
+    let result = sent.clone();
+    ft::runtime::spawn(async move {
+        let ret = file.async_send_file_to(*write, 10, 1_000_000).await;
+        *result.borrow_mut() = Some(ret);
+        drop(write);
+    });
+
+    let received = Rc::new(RefCell::new(Vec::new()));
+    let output = received.clone();
+    ft::runtime::spawn(async move {
+        let mut vec = Vec::new();
+        read.async_read_to_vec(&mut vec).await.unwrap();
+        *output.borrow_mut() = vec;
+    });
+
+    while ft::runtime::run_until_idle().unwrap() != 0 {}
+    assert_eq!(*sent.borrow(), Some(Ok(data.len() - 10)));
+    assert!(*received.borrow() == data[10..]);
+}
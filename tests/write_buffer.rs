@@ -0,0 +1,116 @@
+use std::mem::MaybeUninit;
+
+use ft::collections::WriteBuffer;
+use ft::fd::PipeFlags;
+use ft::{Errno, File};
+
+/// Reads everything that is currently available in the provided non-blocking pipe.
+fn available(read: &File) -> Vec<u8> {
+    let mut buf = [MaybeUninit::uninit(); 256];
+    match read.read(&mut buf) {
+        Ok(n) => buf[..n]
+            .iter()
+            .map(|b| unsafe { b.assume_init() })
+            .collect(),
+        Err(Errno::WOULDBLOCK) => Vec::new(),
+        Err(err) => panic!("{err}"),
+    }
+}
+
+#[test]
+fn buffering() {
+    let (read, write) = File::pipe(PipeFlags::NON_BLOCKING).unwrap();
+    let mut buf = WriteBuffer::with_capacity(*write, 8).unwrap();
+    let filler = vec![b'.'; buf.capacity() - 5];
+
+    write!(buf, "{}-{}", 12, 34).unwrap();
+    assert_eq!(buf.pending(), b"12-34");
+    assert_eq!(available(&read), b"");
+
+    buf.write(&filler).unwrap();
+    assert_eq!(available(&read), b"");
+    buf.write(b"abcd").unwrap();
+    assert_eq!(available(&read), [&b"12-34"[..], &filler].concat());
+    assert_eq!(buf.pending(), b"abcd");
+
+    let large = vec![b'!'; buf.capacity()];
+    assert_eq!(buf.write(&large), Ok(large.len()));
+    assert_eq!(available(&read), [&b"abcd"[..], &large].concat());
+
+    buf.write(b"x").unwrap();
+    buf.flush().unwrap();
+    assert_eq!(available(&read), b"x");
+
+    buf.write(b"y").unwrap();
+    drop(buf);
+    assert_eq!(available(&read), b"y");
+}
+
+#[test]
+fn line_buffered() {
+    let (read, write) = File::pipe(PipeFlags::NON_BLOCKING).unwrap();
+    let mut buf = WriteBuffer::with_capacity(*write, 64).unwrap();
+    buf.set_line_buffered(true);
+
+    buf.write(b"no newline").unwrap();
+    assert_eq!(available(&read), b"");
+    buf.write(b", newline\n").unwrap();
+    assert_eq!(available(&read), b"no newline, newline\n");
+    assert!(buf.pending().is_empty());
+}
+
+/// Fills the provided non-blocking pipe until writing to it would block.
+fn fill(write: &File) {
+    let filler = [b'#'; 4096];
+    loop {
+        match write.write(&filler) {
+            Ok(_) => (),
+            Err(Errno::WOULDBLOCK) => break,
+            Err(err) => panic!("{err}"),
+        }
+    }
+}
+
+/// Reads everything that is currently available in the provided non-blocking pipe.
+fn drain(read: &File) -> Vec<u8> {
+    let mut data = Vec::new();
+    loop {
+        let chunk = available(read);
+        if chunk.is_empty() {
+            break data;
+        }
+        data.extend_from_slice(&chunk);
+    }
+}
+
+#[test]
+fn full_pipe() {
+    let (read, write) = File::pipe(PipeFlags::NON_BLOCKING).unwrap();
+    let mut buf = WriteBuffer::with_capacity(*write, 16).unwrap();
+    buf.set_line_buffered(true);
+
+    // The line is accepted even though it cannot be flushed.
+    fill(&write);
+    assert_eq!(buf.write(b"line\n"), Ok(5));
+    assert_eq!(buf.pending(), b"line\n");
+    drain(&read);
+    buf.flush().unwrap();
+    assert_eq!(drain(&read), b"line\n");
+
+    // Large writes report how much of the data went through before the pipe filled up.
+    let large: Vec<u8> = (0..256 * 1024).map(|i| i as u8).collect();
+    let mut received = Vec::new();
+    let mut rest = &large[..];
+    while !rest.is_empty() {
+        match buf.write(rest) {
+            Ok(count) => {
+                assert!(count > 0 && count <= rest.len());
+                rest = &rest[count..];
+            }
+            Err(Errno::WOULDBLOCK) => (),
+            Err(err) => panic!("{err}"),
+        }
+        received.extend_from_slice(&drain(&read));
+    }
+    assert_eq!(received, large);
+}